@@ -0,0 +1,74 @@
+//! Resolving many addresses with `ElfFormat::address_index` against the linear
+//! `ElfFormat::section_at_address`. Run with `cargo +nightly bench`.
+#![feature(test)]
+extern crate rustep;
+extern crate test;
+
+use std::{convert::TryInto, fs::File, io::prelude::*};
+use rustep::format::elf::{parse_elf, ElfFormat};
+use test::Bencher;
+
+const ADDRESS_COUNT: usize = 100_000;
+
+/// The contents of a bundled fixture.
+fn fixture(path: &str) -> Vec<u8> {
+    let mut buf = Vec::new();
+    File::open(path).unwrap().read_to_end(&mut buf).unwrap();
+    buf
+}
+
+/// `ADDRESS_COUNT` addresses spread over the loaded image of `elf` by a fixed xorshift, so
+/// every run resolves the same ones.
+fn random_addresses(elf: &ElfFormat) -> Vec<u64> {
+    let (start, end) = elf.load_bounds().unwrap();
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..ADDRESS_COUNT)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            start + state % (end - start)
+        })
+        .collect()
+}
+
+#[bench]
+fn bench_section_at_address(b: &mut Bencher) {
+    let buf = fixture("test/test_hash.so");
+    let result = parse_elf(&buf).unwrap();
+    let elf: &ElfFormat = (&result).try_into().unwrap();
+    let addresses = random_addresses(elf);
+
+    b.iter(|| addresses.iter().filter(|&&a| elf.section_at_address(a).is_some()).count());
+}
+
+#[bench]
+fn bench_address_index_build(b: &mut Bencher) {
+    let buf = fixture("test/test_hash.so");
+    let result = parse_elf(&buf).unwrap();
+    let elf: &ElfFormat = (&result).try_into().unwrap();
+
+    b.iter(|| elf.address_index());
+}
+
+#[bench]
+fn bench_address_index_section_at(b: &mut Bencher) {
+    let buf = fixture("test/test_hash.so");
+    let result = parse_elf(&buf).unwrap();
+    let elf: &ElfFormat = (&result).try_into().unwrap();
+    let addresses = random_addresses(elf);
+    let index = elf.address_index();
+
+    b.iter(|| addresses.iter().filter(|&&a| index.section_at(a).is_some()).count());
+}
+
+#[bench]
+fn bench_address_index_symbol_at(b: &mut Bencher) {
+    let buf = fixture("test/test_hash.so");
+    let result = parse_elf(&buf).unwrap();
+    let elf: &ElfFormat = (&result).try_into().unwrap();
+    let addresses = random_addresses(elf);
+    let index = elf.address_index();
+
+    b.iter(|| addresses.iter().filter(|&&a| index.symbol_at(a).is_some()).count());
+}
@@ -0,0 +1,90 @@
+//! Parsing files with many sections, where resolving the section names dominates. Run with
+//! `cargo +nightly bench`.
+#![feature(test)]
+extern crate rustep;
+extern crate test;
+
+use rustep::format::elf::parse_elf;
+use test::Bencher;
+
+/// Length of the unterminated run every name points into in the hostile file
+const RUN_LENGTH: usize = 0x10000;
+
+/// A 64 bits relocatable file with `count` sections besides the null one and `.shstrtab`. With
+/// `names`, each section gets its own `.text.fN` name, otherwise every `sh_name` points into the
+/// same run of `RUN_LENGTH` bytes missing its terminating NUL.
+fn many_sections(count: usize, names: bool) -> Vec<u8> {
+    let mut strtab = vec![0u8];
+    let mut name_offsets = Vec::with_capacity(count);
+    for i in 0..count {
+        if names {
+            name_offsets.push(strtab.len() as u32);
+            strtab.extend_from_slice(format!(".text.f{}\0", i).as_bytes());
+        } else {
+            name_offsets.push(1);
+        }
+    }
+    if !names {
+        strtab.extend(std::iter::repeat(b'a').take(RUN_LENGTH));
+    }
+    let shoff = (64 + strtab.len() + 7) & !7;
+    let shnum = count + 2;
+
+    let mut buf = Vec::with_capacity(shoff + shnum * 64);
+    buf.extend_from_slice(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0");
+    // ET_REL, EM_X86_64, EV_CURRENT, e_entry, e_phoff
+    buf.extend_from_slice(&1u16.to_le_bytes());
+    buf.extend_from_slice(&62u16.to_le_bytes());
+    buf.extend_from_slice(&1u32.to_le_bytes());
+    buf.extend_from_slice(&[0; 16]);
+    buf.extend_from_slice(&(shoff as u64).to_le_bytes());
+    // e_flags, e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+    buf.extend_from_slice(&0u32.to_le_bytes());
+    for &field in [64u16, 56, 0, 64, shnum as u16, 1].iter() {
+        buf.extend_from_slice(&field.to_le_bytes());
+    }
+    buf.extend_from_slice(&strtab);
+    buf.resize(shoff, 0);
+
+    let mut section = |name: u32, sh_type: u32, offset: u64, size: u64| {
+        buf.extend_from_slice(&name.to_le_bytes());
+        buf.extend_from_slice(&sh_type.to_le_bytes());
+        // sh_flags, sh_addr
+        buf.extend_from_slice(&[0; 16]);
+        buf.extend_from_slice(&offset.to_le_bytes());
+        buf.extend_from_slice(&size.to_le_bytes());
+        // sh_link, sh_info, sh_addralign, sh_entsize
+        buf.extend_from_slice(&[0; 24]);
+    };
+    section(0, 0, 0, 0);
+    // SHT_STRTAB
+    section(0, 3, 64, strtab.len() as u64);
+    for &name in name_offsets.iter() {
+        // SHT_PROGBITS
+        section(name, 1, 0, 0);
+    }
+
+    buf
+}
+
+#[bench]
+fn bench_parse_5000_sections(b: &mut Bencher) {
+    let buf = many_sections(5000, true);
+
+    b.iter(|| parse_elf(&buf).unwrap());
+}
+
+#[bench]
+fn bench_parse_20000_sections(b: &mut Bencher) {
+    let buf = many_sections(20000, true);
+
+    b.iter(|| parse_elf(&buf).unwrap());
+}
+
+#[bench]
+fn bench_parse_unterminated_names(b: &mut Bencher) {
+    // Just below `SHN_LORESERVE`, where the count would move to the first section header
+    let buf = many_sections(0xff00 - 2, false);
+
+    b.iter(|| parse_elf(&buf).unwrap());
+}
@@ -0,0 +1,65 @@
+//! Hook for plugging a disassembler into `rustep`.
+//!
+//! No decoder is bundled, bring your own (capstone bindings or a hand written one) by
+//! implementing [`Disassembler`](trait.Disassembler.html). The code to feed it usually comes from
+//! [`ElfFormat::entry_bytes`](../format/elf/trait.ElfFormat.html#method.entry_bytes) or from a
+//! section's data.
+//!
+//! # Examples
+//! ```
+//! #![feature(try_from)]
+//! use std::fs::File;
+//! use std::io::prelude::*;
+//! use std::convert::TryInto;
+//! use rustep::disasm::{Disassembler, Instruction};
+//! use rustep::format::executable::Executable;
+//! use rustep::format::elf::ElfFormat;
+//!
+//! /// Only knows the `xor ebp, ebp` starting `_start`
+//! struct Tiny;
+//!
+//! impl Disassembler for Tiny {
+//!     fn disassemble(&self, code: &[u8], address: u64) -> Vec<Instruction> {
+//!         match code {
+//!             [0x31, 0xed, ..] => vec![Instruction {
+//!                 address: address,
+//!                 bytes: code[..2].to_vec(),
+//!                 mnemonic: "xor".to_string(),
+//!                 operands: "ebp, ebp".to_string(),
+//!             }],
+//!             _ => Vec::new(),
+//!         }
+//!     }
+//! }
+//!
+//! let mut file = File::open("test/test").unwrap();
+//! let mut buf = Vec::new();
+//! file.read_to_end(&mut buf).unwrap();
+//! let res = Executable::from_u8_array(&buf).unwrap();
+//! let elf: &ElfFormat = (&res).try_into().unwrap();
+//!
+//! let code = elf.entry_bytes(16).unwrap();
+//! let insns = Tiny.disassemble(code, elf.header().entry());
+//! assert_eq!(insns[0].mnemonic, "xor");
+//! assert_eq!(insns[0].address, 0x540);
+//! ```
+
+/// A decoded machine instruction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    /// Virtual address of the instruction
+    pub address: u64,
+    /// Raw encoding of the instruction
+    pub bytes: Vec<u8>,
+    /// Mnemonic, e.g. `mov`
+    pub mnemonic: String,
+    /// Operands as text, e.g. `rax, rbx`
+    pub operands: String,
+}
+
+/// An instruction decoder. Implementations decide the architecture and syntax.
+pub trait Disassembler {
+    /// Decodes `code` assumed to be loaded at `address`. Decoding stops at the first byte
+    /// sequence that is not a valid instruction.
+    fn disassemble(&self, code: &[u8], address: u64) -> Vec<Instruction>;
+}
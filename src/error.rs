@@ -1,107 +1,181 @@
 use std::{
+    error,
     fmt,
-    fmt::Display,
 };
 
-use failure::{
-    Context,
-    Fail,
-    Backtrace,
-};
-
-#[derive(Debug)]
-pub struct RustepError {
-    inner: Context<RustepErrorKind>
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Fail)]
-pub enum RustepErrorKind {
-    #[fail(display = "Unsupported ELF class value {}", _0)]
+/// Every error the crate reports.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RustepError {
     UnsupportedElfClass(u8),
-    #[fail(display = "Parsing error")]
-    Parse,
-    #[fail(display = "Not enough byte, {} bytes needed", _0)]
+    /// The class byte is `ELFCLASSNONE` (0), the identification is corrupt
+    InvalidElfClass,
+    /// `offset` is where the structure being parsed starts in the parsed buffer, which is the
+    /// whole file for the headers and symbol tables and the section content for the section
+    /// level parsers. `context` names that structure, `cause` tells what went wrong.
+    Parse { offset: usize, context: &'static str, cause: ParseCause },
     Incomplete(usize),
-    #[fail(display = "Not enough byte, unknown bytes needed")]
-    IncompleteUnknown,
-    #[fail(display = "Segment type {} not resolved", _0)]
+    FileTooSmall { needed: usize, got: usize },
     SegmentType(u64),
-    #[fail(display = "Section type {} not resolved", _0)]
     SectionType(u64),
-    #[fail(display = "Segment flag {} invalid", _0)]
     SegmentFlag(u64),
-    #[fail(display = "Section flag {} invalid", _0)]
     SectionFlag(u64),
-    #[fail(display = "Unknown elf type {}", _0)]
     ElfType(u64),
-    #[fail(display = "Unknown elf machine {}", _0)]
     ElfMachine(u64),
-    #[fail(display = "Not an Elf file")]
     NotElf,
+    /// The buffer does not start with the `ar` archive magic
+    NotArchive,
+    TooManySections(u64),
+    /// More section or program headers are declared than `ParseOptions::max_entries` allows
+    TooManyEntries(u64),
+    InvalidStringIndex(u64),
+    /// The name of the section at this index is not valid UTF-8
+    InvalidSectionName(usize),
+    /// `e_shstrndx` points to this section, which is not a `SHT_STRTAB` one
+    InvalidStringTable(usize),
+    /// There is no section with this name
+    SectionNotFound(String),
+    /// Only `kept` of the `count` sections were parsed, see `ParseOptions::section_filter`
+    FilteredSections { kept: usize, count: u64 },
+    SizeMismatch(u64, u64),
+    /// A table section does not end on an entry boundary
+    PartialEntry { size: u64, entry_size: u64 },
+    UnmappedAddress(u64),
+    SegmentOutOfBounds { index: usize, end: u64, file_size: u64 },
+    /// The segment at `index` breaks an invariant of program headers, `reason` tells which
+    InvalidSegment { index: usize, reason: &'static str },
+    SectionOutOfBounds { index: usize, end: u64, file_size: u64 },
+    /// The program header table starts inside the ELF header or runs into the data of a segment
+    OverlappingHeaders,
 }
 
-impl Fail for RustepError {
-    fn cause(&self) -> Option<&Fail> {
-        self.inner.cause()
-    }
-
-    fn backtrace(&self) -> Option<&Backtrace> {
-        self.inner.backtrace()
-    }
+/// Why a nom parser failed, see `RustepError::Parse`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ParseCause {
+    /// A combinator rejected the input
+    Invalid,
+    /// The input ended early, this many more bytes were needed
+    Incomplete(usize),
+    /// The input ended early
+    IncompleteUnknown,
 }
 
-impl Display for RustepError {
+impl fmt::Display for RustepError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.inner, f)
+        match *self {
+            RustepError::UnsupportedElfClass(class) =>
+                write!(f, "Unsupported ELF class value {}", class),
+            RustepError::InvalidElfClass => write!(f, "Invalid ELF class (ELFCLASSNONE)"),
+            RustepError::Parse { offset, context, .. } =>
+                write!(f, "Parsing error at offset {:#x} ({})", offset, context),
+            RustepError::Incomplete(needed) => write!(f, "Not enough byte, {} bytes needed", needed),
+            RustepError::FileTooSmall { needed, got } =>
+                write!(f, "File too small, need at least {} bytes, got {}", needed, got),
+            RustepError::SegmentType(value) => write!(f, "Segment type {} not resolved", value),
+            RustepError::SectionType(value) => write!(f, "Section type {} not resolved", value),
+            RustepError::SegmentFlag(value) => write!(f, "Segment flag {} invalid", value),
+            RustepError::SectionFlag(value) => write!(f, "Section flag {} invalid", value),
+            RustepError::ElfType(value) => write!(f, "Unknown elf type {}", value),
+            RustepError::ElfMachine(value) => write!(f, "Unknown elf machine {}", value),
+            RustepError::NotElf => write!(f, "Not an Elf file"),
+            RustepError::NotArchive => write!(f, "Not an ar archive"),
+            RustepError::TooManySections(count) =>
+                write!(f, "Too many sections, {} declared", count),
+            RustepError::TooManyEntries(count) =>
+                write!(f, "Too many header table entries, {} declared", count),
+            RustepError::InvalidStringIndex(index) =>
+                write!(f, "String table index {} out of range", index),
+            RustepError::InvalidSectionName(index) =>
+                write!(f, "Name of section {} is not valid UTF-8", index),
+            RustepError::InvalidStringTable(index) =>
+                write!(f, "Section {} holds the section names but is not a string table", index),
+            RustepError::SectionNotFound(ref name) => write!(f, "Section {} not found", name),
+            RustepError::FilteredSections { kept, count } =>
+                write!(f, "Only {} of the {} sections were parsed", kept, count),
+            RustepError::SizeMismatch(expected, given) =>
+                write!(f, "Size mismatch, {} bytes expected, {} bytes given", expected, given),
+            RustepError::PartialEntry { size, entry_size } =>
+                write!(f, "Section size {} is not a multiple of the entry size {}", size, entry_size),
+            RustepError::UnmappedAddress(address) =>
+                write!(f, "Address {:#x} is not mapped from the file", address),
+            RustepError::SegmentOutOfBounds { index, end, file_size } =>
+                write!(f, "Segment {} ends at {:#x}, past the end of file {:#x}",
+                       index, end, file_size),
+            RustepError::InvalidSegment { index, reason } =>
+                write!(f, "Segment {} is invalid, {}", index, reason),
+            RustepError::SectionOutOfBounds { index, end, file_size } =>
+                write!(f, "Section {} ends at {:#x}, past the end of file {:#x}",
+                       index, end, file_size),
+            RustepError::OverlappingHeaders =>
+                write!(f, "Program header table overlaps the ELF header or segment data"),
+        }
     }
 }
 
-impl RustepError {
-    pub fn kind(&self) -> RustepErrorKind {
-        *self.inner.get_context()
+impl error::Error for RustepError {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            RustepError::Parse { ref cause, .. } => Some(cause),
+            _ => None,
+        }
     }
 }
 
-impl From<RustepErrorKind> for RustepError {
-    fn from(kind: RustepErrorKind) -> RustepError {
-        RustepError { inner: Context::new(kind) }
+impl fmt::Display for ParseCause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseCause::Invalid => write!(f, "Invalid input"),
+            ParseCause::Incomplete(needed) => write!(f, "Not enough byte, {} bytes needed", needed),
+            ParseCause::IncompleteUnknown => write!(f, "Not enough byte, unknown bytes needed"),
+        }
     }
 }
 
-impl From<Context<RustepErrorKind>> for RustepError {
-    fn from(inner: Context<RustepErrorKind>) -> RustepError {
-        RustepError { inner: inner }
-    }
-}
+impl error::Error for ParseCause {}
 
-/// Crate `Failure` has not supported `nom`, or `nom` not supported `Failure`.
-/// To avoid to do error handling manually, this macro is used to emulate the try method to be
-/// better to use along with `Failure`.
+/// `nom` results do not convert into our errors, this macro is used to emulate the try method
+/// on them.
+///
+/// The optional second and third arguments are the offset of the parsed structure and a label
+/// naming it. Both failures and incomplete inputs are then reported as a
+/// [`RustepError::Parse`](enum.RustepError.html) carrying them, along with the cause.
 ///
 /// # Examples
 /// ```
 /// use nom::*;
-/// 
+///
 /// let res = tag!(b"abc", "bcd"); // Here will be an IResult returned from nom.
 /// let res_err = nom_try!(res); // This is almost like `res?`, it will return early
 /// let res = tag!(b"abc", "abc"); // This will be accepted
-/// let res_noerr = nom_try!(res); // The Done part of nom will be extracted
+/// let res_noerr = nom_try!(res, 0, "abc tag"); // The Done part of nom will be extracted
 /// ```
 macro_rules! nom_try {
 
     ($arg:expr) => {
+        nom_try!($arg, 0, "input")
+    };
+
+    ($arg:expr, $offset:expr, $context:expr) => {
         match $arg {
             Done(_i, res) => {
                 res
             },
-            Error(e) => {
-                Err(format_err!("Parse Error {}", e.to_string()).context(RustepErrorKind::Parse))?
+            Error(_) => {
+                Err(::error::RustepError::Parse {
+                    offset: $offset as usize,
+                    context: $context,
+                    cause: ::error::ParseCause::Invalid,
+                })?
             },
             Incomplete(needed) => {
-                match needed {
-                    Size(s) => Err(RustepErrorKind::Incomplete(s))?,
-                    Unknown => Err(RustepErrorKind::IncompleteUnknown)?,
-                }
+                let cause = match needed {
+                    Size(s) => ::error::ParseCause::Incomplete(s),
+                    Unknown => ::error::ParseCause::IncompleteUnknown,
+                };
+                Err(::error::RustepError::Parse {
+                    offset: $offset as usize,
+                    context: $context,
+                    cause: cause,
+                })?
             }
         }
     }
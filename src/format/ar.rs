@@ -0,0 +1,217 @@
+//! Unix `ar` archives, the container of static libraries (`.a`). Only the member list is parsed,
+//! each member can then be handed to
+//! [`Executable::from_u8_array`](../executable/enum.Executable.html#method.from_u8_array).
+use std::{borrow::Cow, str};
+use nom::{IResult::*, Needed::{Size, Unknown}, *};
+use error::RustepError;
+use format::executable::Executable;
+
+/// The global header every archive starts with
+pub const AR_MAGIC: &[u8] = b"!<arch>\n";
+/// Size of a member header
+const AR_HEADER_SIZE: usize = 60;
+
+/// A parsed archive, see `Archive::parse`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Archive<'a> {
+    members: Vec<ArchiveMember<'a>>,
+}
+
+/// A file stored in an archive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchiveMember<'a> {
+    /// File name, resolved through the `//` table for long GNU names
+    pub name: Cow<'a, str>,
+    /// Where the contents start in the archive, e.g. for `Executable::from_u8_array_at`
+    pub offset: usize,
+    /// Size of the contents in bytes
+    pub size: usize,
+    data: &'a [u8],
+}
+
+impl<'a> Archive<'a> {
+    /// Reads the member headers of the archive in `input`. The symbol index (`/`, or `/SYM64/`
+    /// for 64-bit offsets) and the long name table (`//`) are not listed as members.
+    pub fn parse(input: &'a [u8]) -> Result<Archive<'a>, RustepError> {
+        if !input.starts_with(AR_MAGIC) {
+            Err(RustepError::NotArchive)?
+        }
+        let mut members = Vec::new();
+        let mut long_names: &[u8] = &[];
+        let mut pos = AR_MAGIC.len();
+        while pos < input.len() {
+            let (raw_name, size) = nom_try!(
+                parse_member_header(&input[pos..]),
+                pos,
+                "archive member header"
+            );
+            let header = pos;
+            let invalid = move || RustepError::Parse {
+                offset: header,
+                context: "archive member header",
+                cause: ::error::ParseCause::Invalid,
+            };
+            let size = str::from_utf8(size)
+                .ok()
+                .and_then(|size| size.trim_end().parse::<usize>().ok())
+                .ok_or_else(invalid)?;
+            let start = pos + AR_HEADER_SIZE;
+            // The header was read, so `start` is within the input
+            if size > input.len() - start {
+                Err(RustepError::Parse {
+                    offset: pos,
+                    context: "archive member",
+                    cause: ::error::ParseCause::Incomplete(size - (input.len() - start)),
+                })?
+            }
+            let data = &input[start..start + size];
+            // Contents are padded to an even offset
+            pos = start + size + size % 2;
+
+            let raw_name = trim_spaces(raw_name);
+            let (name, offset, data) = match raw_name {
+                b"/" | b"/SYM64/" => continue,
+                b"//" => {
+                    long_names = data;
+                    continue;
+                },
+                // GNU long name, an offset in the `//` table where the name ends with `/\n`
+                _ if raw_name.len() > 1 && raw_name[0] == b'/' => {
+                    let name = str::from_utf8(&raw_name[1..])
+                        .ok()
+                        .and_then(|index| index.parse::<usize>().ok())
+                        .and_then(|index| long_names.get(index..))
+                        .map(|name| {
+                            let end = name
+                                .windows(2)
+                                .position(|w| w == b"/\n")
+                                .unwrap_or(name.len());
+                            &name[..end]
+                        })
+                        .ok_or_else(invalid)?;
+                    (name, start, data)
+                },
+                // BSD long name, stored at the start of the contents
+                _ if raw_name.starts_with(b"#1/") => {
+                    let name_size = str::from_utf8(&raw_name[3..])
+                        .ok()
+                        .and_then(|size| size.parse::<usize>().ok())
+                        .filter(|&name_size| name_size <= size)
+                        .ok_or_else(invalid)?;
+                    let name = &data[..name_size];
+                    let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(name_size)];
+                    (name, start + name_size, &data[name_size..])
+                },
+                // GNU short names end with `/`, so they may contain spaces
+                _ if raw_name.ends_with(b"/") => (&raw_name[..raw_name.len() - 1], start, data),
+                _ => (raw_name, start, data),
+            };
+            members.push(ArchiveMember {
+                name: String::from_utf8_lossy(name),
+                offset: offset,
+                size: data.len(),
+                data: data,
+            });
+        }
+
+        Ok(Archive { members: members })
+    }
+
+    /// The files stored in the archive, in order.
+    pub fn members(&self) -> Vec<ArchiveMember<'a>> {
+        self.members.clone()
+    }
+
+    /// The first member called `name`.
+    pub fn member(&self, name: &str) -> Option<&ArchiveMember<'a>> {
+        self.members.iter().find(|member| member.name == name)
+    }
+}
+
+impl<'a> ArchiveMember<'a> {
+    /// Contents of the member, borrowed from the archive.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Parses the member as an executable, usually an object file.
+    pub fn parse(&self) -> Result<Executable<'a>, RustepError> {
+        Executable::from_u8_array(self.data)
+    }
+}
+
+/// Strips the space padding of a header field.
+fn trim_spaces(field: &[u8]) -> &[u8] {
+    let end = field.iter().rposition(|&b| b != b' ').map_or(0, |last| last + 1);
+
+    &field[..end]
+}
+
+// ############### Member header, name and size are all we need ################
+named!(parse_member_header<&[u8], (&[u8], &[u8])>,
+    do_parse!(
+        name: take!(16) >>
+        // date, uid, gid and mode
+        take!(12 + 6 + 6 + 8) >>
+        size: take!(10) >>
+        tag!("`\n") >>
+        (name, size)
+    )
+);
+
+#[test]
+fn test_archive() {
+    use std::{convert::TryInto, fs::File, io::prelude::*};
+    use format::elf::ElfFormat;
+
+    // Checked with `ar tv test/test_archive.a`, which also holds a symbol index
+    let mut file = File::open("test/test_archive.a").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let archive = Archive::parse(&buf).unwrap();
+    let members = archive.members();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].name, "common.o");
+    assert_eq!((members[0].offset, members[0].size), (0xa6, 992));
+    assert_eq!(members[1].name, "debug.o");
+    assert_eq!((members[1].offset, members[1].size), (0x4c2, 3520));
+
+    let mut file = File::open("test/test_debug.o").unwrap();
+    let mut debug = Vec::new();
+    file.read_to_end(&mut debug).unwrap();
+    let member = archive.member("debug.o").unwrap();
+    assert_eq!(member.data(), &debug[..]);
+    let elf = member.parse().unwrap();
+    let elf: &ElfFormat = (&elf).try_into().expect("unable to convert");
+    assert!(elf.section(".rela.text").is_some());
+    for member in members {
+        assert!(Executable::from_u8_array_at(&buf, member.offset).is_ok(), "{}", member.name);
+    }
+
+    // GNU long name through the `//` table, then a BSD long name, with an odd size
+    let header = |name: &str, size: usize| format!("{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                                                   name, 0, 0, 0, 644, size);
+    let mut long = AR_MAGIC.to_vec();
+    let table = "short.o/\na_rather_long_object_name.o/\n";
+    long.extend_from_slice(header("//", table.len()).as_bytes());
+    long.extend_from_slice(table.as_bytes());
+    long.extend_from_slice(header("/9", 3).as_bytes());
+    long.extend_from_slice(b"abc\n");
+    long.extend_from_slice(header("#1/12", 12 + 2).as_bytes());
+    long.extend_from_slice(b"bsd_name.o\0\0xy");
+    let archive = Archive::parse(&long).unwrap();
+    let members = archive.members();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].name, "a_rather_long_object_name.o");
+    assert_eq!(members[0].data(), b"abc");
+    assert_eq!(members[1].name, "bsd_name.o");
+    assert_eq!(members[1].data(), b"xy");
+    assert_eq!(members[1].offset, long.len() - 2);
+
+    assert_eq!(Archive::parse(&debug).err(), Some(RustepError::NotArchive));
+    match Archive::parse(&long[..long.len() - 1]) {
+        Err(RustepError::Parse { context, .. }) => assert_eq!(context, "archive member"),
+        _ => panic!("truncated member accepted"),
+    }
+}
@@ -0,0 +1,168 @@
+//! A format agnostic view of executables, for tools that do not care whether they look at an
+//! `ELF`, a `PE` or a `Mach-O` file. Only `ELF` implements it for now.
+use format::elf::{
+    ElfFormat,
+    ElfMachine,
+    SHN_UNDEF,
+};
+
+/// Processor architecture, independent of the numbering used by each format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    AArch64,
+    Mips,
+    RiscV,
+    PowerPC,
+    Unknown,
+}
+
+impl From<ElfMachine> for Arch {
+    /// Both classes of a family map to the same architecture, e.g. `PPC` and `PPC64` to
+    /// `PowerPC`, except for x86.
+    fn from(machine: ElfMachine) -> Arch {
+        match machine {
+            ElfMachine::I386 => Arch::X86,
+            ElfMachine::X86_64 => Arch::X86_64,
+            ElfMachine::ARM => Arch::Arm,
+            ElfMachine::AARCH64 => Arch::AArch64,
+            ElfMachine::MIPS | ElfMachine::MIPS_RS3_LE => Arch::Mips,
+            ElfMachine::RISCV => Arch::RiscV,
+            ElfMachine::PPC | ElfMachine::PPC64 => Arch::PowerPC,
+            _ => Arch::Unknown,
+        }
+    }
+}
+
+/// A named location, the common ground of the symbol tables of the various formats.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    /// Address of the symbol once loaded, or its value in relocatable objects
+    pub address: u64,
+    /// Size in bytes, 0 when unknown
+    pub size: u64,
+}
+
+/// Methods shared by every supported format. Get one from an
+/// [`Executable`](../executable/enum.Executable.html#method.as_binary).
+pub trait Binary {
+    /// Address execution starts at, 0 if there is none
+    fn entry_point(&self) -> u64;
+    /// Architecture the code is meant for
+    fn arch(&self) -> Arch;
+    /// Whether addresses are 64 bits wide
+    fn is_64bit(&self) -> bool;
+    /// Contents of the first section called `name`
+    fn sections_named(&self, name: &str) -> Option<&[u8]>;
+    /// Named symbols defined in the file
+    fn symbols(&self) -> Vec<Symbol>;
+}
+
+/// `.symtab` is used, or `.dynsym` when the file is stripped.
+impl<T: ElfFormat> Binary for T {
+    fn entry_point(&self) -> u64 {
+        self.header().entry()
+    }
+
+    fn arch(&self) -> Arch {
+        ElfFormat::arch(self)
+    }
+
+    fn is_64bit(&self) -> bool {
+        ElfFormat::is_64bit(self)
+    }
+
+    fn sections_named(&self, name: &str) -> Option<&[u8]> {
+        self.section(name).map(|sec| sec.data())
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        let symbols = if self.is_stripped() {
+            self.dynamic_symbols()
+        } else {
+            ElfFormat::symbols(self)
+        };
+
+        symbols
+            .into_iter()
+            .filter(|sym| !sym.name().is_empty() && sym.section_index() != SHN_UNDEF)
+            .map(|sym| Symbol {
+                name: sym.name().to_string(),
+                address: sym.value(),
+                size: sym.size(),
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_binary() {
+    use std::{fs::File, io::prelude::*};
+    use format::executable::Executable;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = Executable::from_u8_array(&buf).unwrap();
+    let binary = result.as_binary();
+    // Checked with `readelf -h -s test/test`
+    assert_eq!(binary.entry_point(), 0x540);
+    assert_eq!(binary.arch(), Arch::X86_64);
+    assert!(binary.is_64bit());
+    assert_eq!(binary.sections_named(".interp"), Some(&b"/lib64/ld-linux-x86-64.so.2\0"[..]));
+    assert!(binary.sections_named(".nothing").is_none());
+    let symbols = binary.symbols();
+    assert!(symbols.contains(&Symbol { name: "main".to_string(), address: 0x64a, size: 47 }));
+    // printf is imported
+    assert!(symbols.iter().all(|sym| !sym.name.starts_with("printf")));
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = Executable::from_u8_array(&buf).unwrap();
+    let binary = result.as_binary();
+    assert_eq!(binary.arch(), Arch::X86);
+    assert!(!binary.is_64bit());
+
+    // Stripped, the dynamic symbols are left
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = Executable::from_u8_array(&buf).unwrap();
+    assert_eq!(result.as_binary().symbols(), vec![
+        Symbol { name: "answer".to_string(), address: 0x1100, size: 6 },
+    ]);
+}
+
+#[test]
+fn test_arch() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    assert_eq!(Arch::from(ElfMachine::X86_64), Arch::X86_64);
+    assert_eq!(Arch::from(ElfMachine::AARCH64), Arch::AArch64);
+    assert_eq!(Arch::from(ElfMachine::PPC64), Arch::PowerPC);
+    assert_eq!(Arch::from(ElfMachine::S390), Arch::Unknown);
+
+    for &(path, arch) in &[
+        ("test/test", Arch::X86_64),
+        ("test/test32", Arch::X86),
+        ("test/test_aarch64.o", Arch::AArch64),
+        ("test/test_arm.o", Arch::Arm),
+        ("test/test_riscv64.o", Arch::RiscV),
+    ] {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.arch(), arch, "{}", path);
+    }
+}
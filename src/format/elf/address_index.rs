@@ -0,0 +1,139 @@
+//! Sorted views of the sections and symbols of a file for resolving many addresses, see
+//! `ElfFormat::address_index`.
+use format::elf::{ElfFormat, ElfSection, ElfSymbol, SectionType, SymbolType};
+
+/// Address ranges of the allocated sections and of the sized symbols, sorted for `O(log n)`
+/// lookups. Building it is `O(n log n)`, which pays off as soon as more than a handful of
+/// addresses are resolved.
+pub struct AddressIndex<'e> {
+    /// `(start, end, section)`, the ranges do not overlap
+    sections: Vec<(u64, u64, &'e ElfSection)>,
+    /// `(start, end, symbol)`, aliases share the same range
+    symbols: Vec<(u64, u64, &'e ElfSymbol)>,
+}
+
+impl<'e> AddressIndex<'e> {
+    /// Indexes `elf`. Relocatable objects give an empty index, their sections have no address
+    /// yet. Like in `ElfFormat::section_at_address`, empty sections contain nothing, and neither
+    /// does `.tbss`, which takes no room in the memory image. Symbols come from `.symtab`, or
+    /// from `.dynsym` when the file is stripped; only defined ones with a size are kept.
+    pub fn new<T: ElfFormat + ?Sized>(elf: &'e T) -> AddressIndex<'e> {
+        if elf.is_relocatable() {
+            return AddressIndex { sections: Vec::new(), symbols: Vec::new() };
+        }
+
+        let mut sections: Vec<(u64, u64, &ElfSection)> = elf.sections()
+            .into_iter()
+            .filter(|sec| {
+                sec.is_alloc()
+                    && sec.shdr().size() != 0
+                    && !(sec.is_tls() && *sec.section_type() == SectionType::SHT_NOBITS)
+            })
+            .map(|sec| {
+                let shdr = sec.shdr();
+                (shdr.address(), shdr.address().saturating_add(shdr.size()), sec)
+            })
+            .collect();
+        sections.sort_by_key(|&(start, _, _)| start);
+
+        let symbols = if elf.is_stripped() { elf.dynamic_symbols() } else { elf.symbols() };
+        let mut symbols: Vec<(u64, u64, &ElfSymbol)> = symbols
+            .into_iter()
+            .filter(|sym| {
+                sym.size() != 0
+                    && elf.symbol_location(*sym).is_some()
+                    && sym.sym_type() != SymbolType::STT_TLS
+            })
+            .map(|sym| (sym.value(), sym.value().saturating_add(sym.size()), sym))
+            .collect();
+        symbols.sort_by_key(|&(start, _, _)| start);
+
+        AddressIndex { sections: sections, symbols: symbols }
+    }
+
+    /// The allocated section containing `address`.
+    pub fn section_at(&self, address: u64) -> Option<&'e ElfSection> {
+        lookup(&self.sections, address)
+    }
+
+    /// The symbol containing `address`, the last one in symbol table order when several
+    /// aliases do.
+    pub fn symbol_at(&self, address: u64) -> Option<&'e ElfSymbol> {
+        lookup(&self.symbols, address)
+    }
+}
+
+/// The range of `ranges`, sorted by start, with the closest start at or before `address`,
+/// provided it extends up to `address`.
+fn lookup<'e, T: ?Sized>(ranges: &[(u64, u64, &'e T)], address: u64) -> Option<&'e T> {
+    let after = ranges.partition_point(|&(start, _, _)| start <= address);
+    let &(_, end, item) = ranges.get(after.checked_sub(1)?)?;
+    if address < end {
+        Some(item)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_address_index() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    for path in ["test/test", "test/test32", "test/test_nopie", "test/test_now.so"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let index = res.address_index();
+        // The same answers as the linear scan, around every section boundary
+        for sec in res.sections() {
+            let start = sec.shdr().address();
+            let end = start + sec.shdr().size();
+            for &address in [start.saturating_sub(1), start, end.saturating_sub(1), end].iter() {
+                assert_eq!(
+                    index.section_at(address).map(|sec| sec.index()),
+                    res.section_at_address(address).map(|sec| sec.index()),
+                    "{} {:#x}", path, address
+                );
+            }
+        }
+    }
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let index = res.address_index();
+    // `main` is 47 bytes at 0x64a
+    assert_eq!(index.symbol_at(0x64a).map(|sym| sym.name()), Some("main"));
+    assert_eq!(index.symbol_at(0x64a + 46).map(|sym| sym.name()), Some("main"));
+    assert_ne!(index.symbol_at(0x64a + 47).map(|sym| sym.name()), Some("main"));
+    assert_eq!(index.section_at(0x64a).map(|sec| sec.name()), Some(".text"));
+    assert!(index.section_at(0xffffffff).is_none());
+    assert!(index.symbol_at(0).is_none());
+
+    // Stripped, `answer` comes from `.dynsym`
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.address_index().symbol_at(0x1105).map(|sym| sym.name()), Some("answer"));
+
+    // Nothing has an address in relocatable objects
+    let mut file = File::open("test/test_debug.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let index = res.address_index();
+    assert!(index.section_at(0).is_none());
+    assert!(index.symbol_at(0).is_none());
+}
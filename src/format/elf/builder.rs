@@ -0,0 +1,147 @@
+//! In-place patching of a parsed ELF.
+use std::convert::TryInto;
+use error::RustepError;
+use format::executable::Executable;
+use format::elf::{ElfFormat, MaybeKnown, SectionType, SegmentType};
+
+/// Patches the bytes of a parsed ELF and writes out the result. Only same-size edits are
+/// supported for now, so no offset or size in the headers ever has to move.
+///
+/// # Examples
+/// ```
+/// #![feature(try_from)]
+/// use std::fs::File;
+/// use std::io::prelude::*;
+/// use rustep::format::executable::Executable;
+/// use rustep::format::elf::ElfBuilder;
+///
+/// let mut file = File::open("test/test").unwrap();
+/// let mut buf = Vec::new();
+/// file.read_to_end(&mut buf).unwrap();
+///
+/// let exe = Executable::from_u8_array(&buf).unwrap();
+/// let mut builder = ElfBuilder::new(&exe).unwrap();
+/// // `ret` at the entry point
+/// builder.patch(0x540, &[0xc3]).unwrap();
+/// let patched = builder.build();
+/// assert_eq!(patched[0x540], 0xc3);
+/// ```
+pub struct ElfBuilder {
+    /// The file being patched
+    image: Vec<u8>,
+    /// (name, type, file offset, size) of every section
+    sections: Vec<(String, MaybeKnown<SectionType>, u64, u64)>,
+    /// (virtual address, file offset, file size) of every `PT_LOAD` segment
+    loads: Vec<(u64, u64, u64)>,
+}
+
+impl ElfBuilder {
+    /// Starts from the file as parsed, see `ElfFormat::write`.
+    pub fn new(executable: &Executable) -> Result<ElfBuilder, RustepError> {
+        let elf: &ElfFormat = executable.try_into()?;
+        let mut image = Vec::new();
+        elf.write(&mut image)?;
+        let sections = elf.sections()
+            .iter()
+            .map(|sec| {
+                let shdr = sec.shdr();
+                (sec.name().to_string(), *sec.section_type(), shdr.offset(), shdr.size())
+            })
+            .collect();
+        let loads = elf.segments()
+            .iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .map(|seg| {
+                let phdr = seg.phdr();
+                (phdr.vaddr(), phdr.offset(), phdr.file_size())
+            })
+            .collect();
+
+        Ok(ElfBuilder {
+            image: image,
+            sections: sections,
+            loads: loads,
+        })
+    }
+
+    /// Replaces the contents of the named section. `data` must have the size of the section,
+    /// and the section must have contents in the file (not `SHT_NOBITS`).
+    pub fn set_section_data(&mut self, name: &str, data: Vec<u8>) -> Result<(), RustepError> {
+        let &(_, section_type, offset, size) = self.sections
+            .iter()
+            .find(|sec| sec.0 == name)
+            .ok_or_else(|| RustepError::SectionNotFound(name.to_string()))?;
+        if section_type == SectionType::SHT_NOBITS {
+            Err(RustepError::SizeMismatch(0, data.len() as u64))?
+        }
+        if data.len() as u64 != size {
+            Err(RustepError::SizeMismatch(size, data.len() as u64))?
+        }
+        self.write_at(offset, &data)
+    }
+
+    /// Overwrites the bytes loaded at virtual address `vaddr`. The whole range must be backed
+    /// by the file contents of a single `PT_LOAD` segment.
+    pub fn patch(&mut self, vaddr: u64, bytes: &[u8]) -> Result<(), RustepError> {
+        let len = bytes.len() as u64;
+        let offset = self.loads
+            .iter()
+            .find(|&&(start, _, size)| vaddr >= start && vaddr - start + len <= size)
+            .map(|&(start, offset, _)| offset + (vaddr - start))
+            .ok_or(RustepError::UnmappedAddress(vaddr))?;
+        self.write_at(offset, bytes)
+    }
+
+    /// The patched file.
+    pub fn build(self) -> Vec<u8> {
+        self.image
+    }
+
+    fn write_at(&mut self, offset: u64, bytes: &[u8]) -> Result<(), RustepError> {
+        let start = offset as usize;
+        let end = start + bytes.len();
+        if end > self.image.len() {
+            Err(RustepError::Incomplete(end - self.image.len()))?
+        }
+        self.image[start..end].copy_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_builder() {
+    use std::{fs::File, io::prelude::*};
+    use format::elf::parse_elf;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let exe = parse_elf(&buf).unwrap();
+    let mut builder = ElfBuilder::new(&exe).unwrap();
+    // `main` is at 0x64a in `.text`, make it `xor eax, eax; ret`
+    builder.patch(0x64a, &[0x31, 0xc0, 0xc3]).unwrap();
+    let comment = vec![b'x'; 0x2b];
+    builder.set_section_data(".comment", comment.clone()).unwrap();
+    // Same-size edits only
+    assert!(builder.set_section_data(".comment", vec![0; 4]).is_err());
+    assert!(builder.set_section_data(".bss", vec![0; 8]).is_err());
+    assert!(builder.set_section_data(".nonexistent", vec![]).is_err());
+    // Past the end of the code segment
+    assert!(builder.patch(0x82f, &[0, 0]).is_err());
+    assert!(builder.patch(0x100000, &[0]).is_err());
+    let patched = builder.build();
+    assert_eq!(patched.len(), buf.len());
+
+    let result = parse_elf(&patched).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let text = res.section(".text").unwrap();
+    let main = (0x64a - text.shdr().address()) as usize;
+    assert_eq!(&text.data()[main..main + 3], &[0x31, 0xc0, 0xc3]);
+    assert_eq!(res.section(".comment").unwrap().data(), &comment[..]);
+    // Everything else is untouched
+    assert_eq!(&patched[..0x64a], &buf[..0x64a]);
+    assert_eq!(&patched[0x64d..0x1030], &buf[0x64d..0x1030]);
+    assert_eq!(&patched[0x105b..], &buf[0x105b..]);
+}
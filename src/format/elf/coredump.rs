@@ -0,0 +1,231 @@
+//! Process state recorded in the notes of core dumps (`ET_CORE`).
+use nom::*;
+
+/// Offset of `pr_reg` in the x86-64 `struct elf_prstatus`
+const X86_64_PRSTATUS_REG_OFFSET: usize = 112;
+
+/// General purpose registers of a thread, as saved in its `NT_PRSTATUS` note.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Registers {
+    X86_64(X86_64Registers),
+}
+
+/// x86-64 registers, in the `struct user_regs_struct` layout.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct X86_64Registers {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub rbp: u64,
+    pub rbx: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rax: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    /// Syscall number the thread was in, `-1` if none
+    pub orig_rax: u64,
+    pub rip: u64,
+    pub cs: u64,
+    pub eflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+    pub fs_base: u64,
+    pub gs_base: u64,
+    pub ds: u64,
+    pub es: u64,
+    pub fs: u64,
+    pub gs: u64,
+}
+
+/// The process a core dump comes from, as described by its `NT_PRPSINFO` note.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub uid: u32,
+    pub gid: u32,
+    /// Name of the executable, truncated to 15 bytes by the kernel
+    pub name: String,
+    /// Start of the command line, truncated to 79 bytes by the kernel
+    pub args: String,
+}
+
+/// A file mapped in the address space of the process a core dump comes from, as listed by its
+/// `NT_FILE` note.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MappedFile {
+    /// First address of the mapping
+    pub start: u64,
+    /// Address right after the mapping
+    pub end: u64,
+    /// Offset in the file the mapping starts at, in bytes
+    pub file_offset: u64,
+    pub path: String,
+}
+
+/// Parses the descriptor of an x86-64 `NT_PRSTATUS` note.
+pub(crate) fn parse_x86_64_prstatus(desc: &[u8]) -> Option<X86_64Registers> {
+    let regs = count!(desc.get(X86_64_PRSTATUS_REG_OFFSET..)?, le_u64, 27)
+        .to_result()
+        .ok()?;
+
+    Some(X86_64Registers {
+        r15: regs[0],
+        r14: regs[1],
+        r13: regs[2],
+        r12: regs[3],
+        rbp: regs[4],
+        rbx: regs[5],
+        r11: regs[6],
+        r10: regs[7],
+        r9: regs[8],
+        r8: regs[9],
+        rax: regs[10],
+        rcx: regs[11],
+        rdx: regs[12],
+        rsi: regs[13],
+        rdi: regs[14],
+        orig_rax: regs[15],
+        rip: regs[16],
+        cs: regs[17],
+        eflags: regs[18],
+        rsp: regs[19],
+        ss: regs[20],
+        fs_base: regs[21],
+        gs_base: regs[22],
+        ds: regs[23],
+        es: regs[24],
+        fs: regs[25],
+        gs: regs[26],
+    })
+}
+
+/// Parses the descriptor of a `NT_PRPSINFO` note. 64-bit Linux targets share one layout, on
+/// 32-bit ones the i386 layout with 16 bits ids is the only supported one.
+pub(crate) fn parse_prpsinfo(desc: &[u8], is_64: bool) -> Option<ProcessInfo> {
+    // `pr_flag` is a long, the ids follow it
+    let ((uid, gid), ids) = if is_64 {
+        let ids = desc.get(16..)?;
+        (tuple!(ids, le_u32, le_u32).to_result().ok()?, &ids[8..])
+    } else {
+        let ids = desc.get(8..)?;
+        let (uid, gid) = tuple!(ids, le_u16, le_u16).to_result().ok()?;
+        ((uid as u32, gid as u32), &ids[4..])
+    };
+    let (pid, ppid) = tuple!(ids, le_u32, le_u32).to_result().ok()?;
+    // Skip `pr_pgrp` and `pr_sid`
+    let name = ids.get(16..32)?;
+    let args = ids.get(32..112)?;
+
+    Some(ProcessInfo {
+        pid: pid,
+        ppid: ppid,
+        uid: uid,
+        gid: gid,
+        name: c_string(name),
+        args: c_string(args),
+    })
+}
+
+/// Parses the descriptor of a `NT_FILE` note. All the numbers are longs, the file offsets are
+/// given in pages.
+pub(crate) fn parse_file_note(desc: &[u8], is_64: bool) -> Option<Vec<MappedFile>> {
+    let word = |input| if is_64 { le_u64(input) } else { le_u32(input).map(|w| w as u64) };
+    let word_size = if is_64 { 8 } else { 4 };
+    let (count, page_size) = tuple!(desc, word, word).to_result().ok()?;
+    let ranges = desc.get(word_size * 2..)?;
+    let ranges = count!(ranges, tuple!(word, word, word), count as usize).to_result().ok()?;
+    let paths = desc.get(word_size * (2 + 3 * count as usize)..)?.split(|&b| b == 0);
+
+    Some(ranges
+        .into_iter()
+        .zip(paths)
+        .map(|((start, end, offset), path)| MappedFile {
+            start: start,
+            end: end,
+            file_offset: offset * page_size,
+            path: String::from_utf8_lossy(path).into_owned(),
+        })
+        .collect())
+}
+
+/// A NUL padded fixed size string, the kernel also pads `pr_psargs` with a space.
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim_end().to_string()
+}
+
+#[test]
+fn test_thread_registers() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test_core").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Taken from the raw `NT_PRSTATUS` note, the program crashed writing to a null pointer
+    let threads = res.thread_registers();
+    assert_eq!(threads.len(), 1);
+    let Registers::X86_64(regs) = threads[0];
+    assert_eq!(regs.rip, 0x5591b8ebf139);
+    assert_eq!(regs.rsp, 0x7fff459e49f0);
+    assert_eq!(regs.rbp, 0x7fff459e49f0);
+    assert_eq!(regs.rdi, 1);
+    assert_eq!(regs.orig_rax, u64::max_value());
+    assert_eq!(regs.eflags, 0x10246);
+    assert_eq!(regs.cs, 0x33);
+    assert_eq!(regs.fs_base, 0x7f64d1afb740);
+
+    assert_eq!(res.process_info(), Some(ProcessInfo {
+        pid: 29157,
+        ppid: 29045,
+        uid: 0,
+        gid: 0,
+        name: "crash".to_string(),
+        args: "./crash".to_string(),
+    }));
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.thread_registers().is_empty());
+    assert_eq!(res.process_info(), None);
+}
+
+#[test]
+fn test_mapped_files() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test_core").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Taken from the raw `NT_FILE` note, the page size is 4096
+    let files = res.mapped_files();
+    assert_eq!(files.len(), 15);
+    assert_eq!(files[0], MappedFile {
+        start: 0x5591b8ebe000,
+        end: 0x5591b8ebf000,
+        file_offset: 0,
+        path: "/tmp/crash".to_string(),
+    });
+    assert_eq!(files[6].path, "/usr/lib/x86_64-linux-gnu/libc.so.6");
+    assert_eq!(files[6].file_offset, 0x26000);
+    assert_eq!(files[14].path, "/usr/lib/x86_64-linux-gnu/ld-linux-x86-64.so.2");
+    assert_eq!(files[14].end, 0x7f64d1d27000);
+}
@@ -0,0 +1,259 @@
+//! Dynamic section (`PT_DYNAMIC`) entries.
+use format::elf::{MaybeKnown, ReservedRanges};
+use nom::{IResult::*, Needed::{Size, Unknown}, *};
+use error::RustepError;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Dynamic entry tag, refer to `d_tag`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum DynamicTag {
+    DT_NULL = 0,
+    DT_NEEDED = 1,
+    DT_PLTRELSZ = 2,
+    DT_PLTGOT = 3,
+    DT_HASH = 4,
+    DT_STRTAB = 5,
+    DT_SYMTAB = 6,
+    DT_RELA = 7,
+    DT_RELASZ = 8,
+    DT_RELAENT = 9,
+    DT_STRSZ = 10,
+    DT_SYMENT = 11,
+    DT_INIT = 12,
+    DT_FINI = 13,
+    DT_SONAME = 14,
+    DT_RPATH = 15,
+    DT_SYMBOLIC = 16,
+    DT_REL = 17,
+    DT_RELSZ = 18,
+    DT_RELENT = 19,
+    DT_PLTREL = 20,
+    DT_DEBUG = 21,
+    DT_TEXTREL = 22,
+    DT_JMPREL = 23,
+    DT_BIND_NOW = 24,
+    DT_INIT_ARRAY = 25,
+    DT_FINI_ARRAY = 26,
+    DT_INIT_ARRAYSZ = 27,
+    DT_FINI_ARRAYSZ = 28,
+    DT_RUNPATH = 29,
+    DT_FLAGS = 30,
+    DT_PREINIT_ARRAY = 32,
+    DT_PREINIT_ARRAYSZ = 33,
+    DT_SYMTAB_SHNDX = 34,
+    DT_RELRSZ = 35,
+    DT_RELR = 36,
+    DT_RELRENT = 37,
+    DT_GNU_PRELINKED = 0x6ffffdf5,
+    DT_GNU_CONFLICTSZ = 0x6ffffdf6,
+    DT_GNU_LIBLISTSZ = 0x6ffffdf7,
+    DT_CHECKSUM = 0x6ffffdf8,
+    DT_PLTPADSZ = 0x6ffffdf9,
+    DT_MOVEENT = 0x6ffffdfa,
+    DT_MOVESZ = 0x6ffffdfb,
+    DT_FEATURE_1 = 0x6ffffdfc,
+    DT_POSFLAG_1 = 0x6ffffdfd,
+    DT_SYMINSZ = 0x6ffffdfe,
+    DT_SYMINENT = 0x6ffffdff,
+    DT_GNU_HASH = 0x6ffffef5,
+    DT_TLSDESC_PLT = 0x6ffffef6,
+    DT_TLSDESC_GOT = 0x6ffffef7,
+    DT_GNU_CONFLICT = 0x6ffffef8,
+    DT_GNU_LIBLIST = 0x6ffffef9,
+    DT_CONFIG = 0x6ffffefa,
+    DT_DEPAUDIT = 0x6ffffefb,
+    DT_AUDIT = 0x6ffffefc,
+    DT_PLTPAD = 0x6ffffefd,
+    DT_MOVETAB = 0x6ffffefe,
+    DT_SYMINFO = 0x6ffffeff,
+    DT_VERSYM = 0x6ffffff0,
+    DT_RELACOUNT = 0x6ffffff9,
+    DT_RELCOUNT = 0x6ffffffa,
+    DT_FLAGS_1 = 0x6ffffffb,
+    DT_VERDEF = 0x6ffffffc,
+    DT_VERDEFNUM = 0x6ffffffd,
+    DT_VERNEED = 0x6ffffffe,
+    DT_VERNEEDNUM = 0x6fffffff,
+    DT_AUXILIARY = 0x7ffffffd,
+    DT_FILTER = 0x7fffffff,
+}
+
+/// `DF_1_PIE` bit of `DT_FLAGS_1`, set by the linker on position independent executables
+pub const DF_1_PIE: u64 = 0x08000000;
+
+impl ReservedRanges for DynamicTag {
+    const RANGES: &'static [(u32, u32, &'static str)] = &[
+        (0x6000000d, 0x6ffff000, "DT_LOOS"),
+        (0x70000000, 0x7fffffff, "DT_LOPROC"),
+    ];
+}
+
+bitflags! {
+    /// Bits of `DT_FLAGS`
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct DynamicFlag: u64 {
+        const DF_ORIGIN = 0x1;
+        const DF_SYMBOLIC = 0x2;
+        const DF_TEXTREL = 0x4;
+        const DF_BIND_NOW = 0x8;
+        const DF_STATIC_TLS = 0x10;
+    }
+}
+
+bitflags! {
+    /// Bits of `DT_FLAGS_1`
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct DynamicFlag1: u64 {
+        const DF_1_NOW = 0x1;
+        const DF_1_GLOBAL = 0x2;
+        const DF_1_GROUP = 0x4;
+        const DF_1_NODELETE = 0x8;
+        const DF_1_LOADFLTR = 0x10;
+        const DF_1_INITFIRST = 0x20;
+        const DF_1_NOOPEN = 0x40;
+        const DF_1_ORIGIN = 0x80;
+        const DF_1_DIRECT = 0x100;
+        const DF_1_TRANS = 0x200;
+        const DF_1_INTERPOSE = 0x400;
+        const DF_1_NODEFLIB = 0x800;
+        const DF_1_NODUMP = 0x1000;
+        const DF_1_CONFALT = 0x2000;
+        const DF_1_ENDFILTEE = 0x4000;
+        const DF_1_DISPRELDNE = 0x8000;
+        const DF_1_DISPRELPND = 0x10000;
+        const DF_1_NODIRECT = 0x20000;
+        const DF_1_IGNMULDEF = 0x40000;
+        const DF_1_NOKSYMS = 0x80000;
+        const DF_1_NOHDR = 0x100000;
+        const DF_1_EDITED = 0x200000;
+        const DF_1_NORELOC = 0x400000;
+        const DF_1_SYMINTPOSE = 0x800000;
+        const DF_1_GLOBAUDIT = 0x1000000;
+        const DF_1_SINGLETON = 0x2000000;
+        const DF_1_STUB = 0x4000000;
+        const DF_1_PIE = 0x8000000;
+    }
+}
+
+/// Renders `DT_FLAGS` bits into their symbolic names, in bit order. Unknown bits are skipped.
+///
+/// # Examples
+/// ```
+/// use rustep::format::elf::{decode_dynamic_flags, DynamicFlag};
+///
+/// let flags = DynamicFlag::DF_TEXTREL | DynamicFlag::DF_BIND_NOW;
+/// assert_eq!(decode_dynamic_flags(flags), vec!["DF_TEXTREL", "DF_BIND_NOW"]);
+/// ```
+pub fn decode_dynamic_flags(flags: DynamicFlag) -> Vec<String> {
+    flags.iter_names().map(|(name, _)| name.to_string()).collect()
+}
+
+/// Renders `DT_FLAGS_1` bits into their symbolic names, in bit order. Unknown bits are skipped.
+pub fn decode_dynamic_flags_1(flags: DynamicFlag1) -> Vec<String> {
+    flags.iter_names().map(|(name, _)| name.to_string()).collect()
+}
+
+/// A single dynamic entry. 32-bit and 64-bit entries are widened into this same representation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DynamicEntry {
+    tag: MaybeKnown<DynamicTag>,
+    value: u64,
+}
+
+impl DynamicEntry {
+    fn new(tag: u64, value: u64) -> DynamicEntry {
+        let tag = if tag <= u32::max_value() as u64 {
+            MaybeKnown::from_u32(tag as u32)
+        } else {
+            // No defined tag is that large
+            MaybeKnown::Unknown(tag as u32)
+        };
+
+        DynamicEntry {
+            tag: tag,
+            value: value,
+        }
+    }
+
+    /// `d_tag`, unknown tags keep their raw value
+    pub fn tag(&self) -> &MaybeKnown<DynamicTag> {
+        &self.tag
+    }
+
+    /// `d_un`, either an address or a plain value depending on the tag
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// Parses the content of the dynamic segment, 32-bit version. The entries after `DT_NULL` are
+/// padding and are dropped.
+pub(crate) fn parse_dynamic32(data: &[u8]) -> Result<Vec<DynamicEntry>, RustepError> {
+    let entries = nom_try!(
+        count!(data, parse_elf_dyn32, data.len() / 8),
+        0,
+        "dynamic entries"
+    );
+    Ok(entries
+        .iter()
+        .map(|&(tag, value)| DynamicEntry::new(tag as u64, value as u64))
+        .take_while(|entry| entry.tag != DynamicTag::DT_NULL)
+        .collect())
+}
+
+/// Parses the content of the dynamic segment, 64-bit version. The entries after `DT_NULL` are
+/// padding and are dropped.
+pub(crate) fn parse_dynamic64(data: &[u8]) -> Result<Vec<DynamicEntry>, RustepError> {
+    let entries = nom_try!(
+        count!(data, parse_elf_dyn64, data.len() / 16),
+        0,
+        "dynamic entries"
+    );
+    Ok(entries
+        .iter()
+        .map(|&(tag, value)| DynamicEntry::new(tag, value))
+        .take_while(|entry| entry.tag != DynamicTag::DT_NULL)
+        .collect())
+}
+
+// ############### Elf Dynamic 32 ################
+named!(parse_elf_dyn32<&[u8], (u32, u32)>,
+    tuple!(le_u32, le_u32)
+);
+
+// ############### Elf Dynamic 64 ################
+named!(parse_elf_dyn64<&[u8], (u64, u64)>,
+    tuple!(le_u64, le_u64)
+);
+
+#[test]
+fn test_dynamic() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -d test/test`, which also counts the terminating `DT_NULL`
+    let dynamic = res.dynamic().unwrap();
+    assert_eq!(dynamic.len(), 25);
+    assert_eq!(*dynamic[0].tag(), DynamicTag::DT_NEEDED);
+    assert_eq!(dynamic[0].value(), 1);
+    let flags_1 = dynamic.iter().find(|d| *d.tag() == DynamicTag::DT_FLAGS_1).unwrap();
+    assert_eq!(flags_1.value(), 0x08000000);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let dynamic = res.dynamic().unwrap();
+    assert_eq!(*dynamic[1].tag(), DynamicTag::DT_INIT);
+    assert_eq!(dynamic[1].value(), 0x37c);
+}
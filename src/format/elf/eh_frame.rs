@@ -0,0 +1,190 @@
+//! The `.eh_frame_hdr` section (`PT_GNU_EH_FRAME` segment), which indexes the FDEs of
+//! `.eh_frame` by the address of the code they describe.
+use nom::*;
+
+/// No value is present
+pub const DW_EH_PE_OMIT: u8 = 0xff;
+
+/// A parsed `.eh_frame_hdr`, see `ElfFormat::eh_frame_hdr`. All the pointers are decoded into
+/// virtual addresses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EhFrameHdr {
+    /// Format version, always 1
+    pub version: u8,
+    /// `DW_EH_PE_*` encoding of `eh_frame_ptr`
+    pub eh_frame_ptr_encoding: u8,
+    /// `DW_EH_PE_*` encoding of the FDE count
+    pub fde_count_encoding: u8,
+    /// `DW_EH_PE_*` encoding of the search table entries
+    pub table_encoding: u8,
+    /// Address of `.eh_frame`
+    pub eh_frame_ptr: u64,
+    /// `(initial location, FDE address)` pairs sorted by initial location, i.e. the address of
+    /// the first instruction covered by each FDE. Empty when the table is omitted.
+    pub table: Vec<(u64, u64)>,
+}
+
+impl EhFrameHdr {
+    /// Address of the FDE covering `address`, i.e. the one with the closest initial location at
+    /// or before it. Whether the FDE really extends up to `address` is only known from the FDE
+    /// itself.
+    pub fn lookup(&self, address: u64) -> Option<u64> {
+        let after = match self.table.binary_search_by_key(&address, |&(location, _)| location) {
+            Ok(found) => return Some(self.table[found].1),
+            Err(after) => after,
+        };
+        if after == 0 {
+            None
+        } else {
+            Some(self.table[after - 1].1)
+        }
+    }
+}
+
+/// Parses `data`, the contents of `.eh_frame_hdr` loaded at `address`. `is_64` gives the size
+/// of `DW_EH_PE_absptr` values. `None` if the header is malformed or uses an encoding which
+/// cannot be resolved from the section alone (`DW_EH_PE_textrel`, `funcrel`, `aligned` or
+/// `indirect`).
+pub(crate) fn parse_eh_frame_hdr(data: &[u8], address: u64, is_64: bool) -> Option<EhFrameHdr> {
+    let (version, eh_frame_ptr_encoding, fde_count_encoding, table_encoding) =
+        tuple!(data, le_u8, le_u8, le_u8, le_u8).to_result().ok()?;
+    if version != 1 {
+        return None;
+    }
+    let reader = PointerReader { data: data, address: address, is_64: is_64 };
+    let (eh_frame_ptr, pos) = reader.read(4, eh_frame_ptr_encoding)?;
+    let mut table = Vec::new();
+    if fde_count_encoding != DW_EH_PE_OMIT && table_encoding != DW_EH_PE_OMIT {
+        let (count, mut pos) = reader.read(pos, fde_count_encoding)?;
+        for _ in 0..count {
+            let (location, next) = reader.read(pos, table_encoding)?;
+            let (fde, next) = reader.read(next, table_encoding)?;
+            table.push((location, fde));
+            pos = next;
+        }
+    }
+
+    Some(EhFrameHdr {
+        version: version,
+        eh_frame_ptr_encoding: eh_frame_ptr_encoding,
+        fde_count_encoding: fde_count_encoding,
+        table_encoding: table_encoding,
+        eh_frame_ptr: eh_frame_ptr,
+        table: table,
+    })
+}
+
+/// Decodes `DW_EH_PE_*` encoded pointers of a section loaded at `address`.
+struct PointerReader<'a> {
+    data: &'a [u8],
+    address: u64,
+    is_64: bool,
+}
+
+impl<'a> PointerReader<'a> {
+    /// Reads the value at `pos`, giving it along with the position following it.
+    fn read(&self, pos: usize, encoding: u8) -> Option<(u64, usize)> {
+        let input = self.data.get(pos..)?;
+        let (value, size) = match encoding & 0x0f {
+            // DW_EH_PE_absptr
+            0x00 if self.is_64 => (le_u64(input).to_result().ok()?, 8),
+            0x00 => (le_u32(input).to_result().ok()? as u64, 4),
+            // DW_EH_PE_uleb128, DW_EH_PE_sleb128
+            0x01 => read_leb128(input, false)?,
+            0x09 => read_leb128(input, true)?,
+            // DW_EH_PE_udata2, DW_EH_PE_udata4, DW_EH_PE_udata8
+            0x02 => (le_u16(input).to_result().ok()? as u64, 2),
+            0x03 => (le_u32(input).to_result().ok()? as u64, 4),
+            0x04 => (le_u64(input).to_result().ok()?, 8),
+            // DW_EH_PE_sdata2, DW_EH_PE_sdata4, DW_EH_PE_sdata8
+            0x0a => (le_i16(input).to_result().ok()? as u64, 2),
+            0x0b => (le_i32(input).to_result().ok()? as u64, 4),
+            0x0c => (le_i64(input).to_result().ok()? as u64, 8),
+            _ => return None,
+        };
+        let base = match encoding & 0xf0 {
+            // DW_EH_PE_absptr
+            0x00 => 0,
+            // DW_EH_PE_pcrel
+            0x10 => self.address.wrapping_add(pos as u64),
+            // DW_EH_PE_datarel, relative to the start of `.eh_frame_hdr`
+            0x30 => self.address,
+            _ => return None,
+        };
+
+        Some((base.wrapping_add(value), pos + size))
+    }
+}
+
+/// Reads a LEB128 value, giving it along with its size.
+fn read_leb128(input: &[u8], signed: bool) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    for (i, &byte) in input.iter().enumerate() {
+        if shift < 64 {
+            value |= ((byte & 0x7f) as u64) << shift;
+        }
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if signed && shift < 64 && byte & 0x40 != 0 {
+                value |= !0 << shift;
+            }
+            return Some((value, i + 1));
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_eh_frame_hdr() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf --debug-dump=frames test/test`
+    let hdr = res.eh_frame_hdr().unwrap();
+    assert_eq!(hdr.version, 1);
+    // DW_EH_PE_pcrel | DW_EH_PE_sdata4, DW_EH_PE_udata4, DW_EH_PE_datarel | DW_EH_PE_sdata4
+    assert_eq!(
+        (hdr.eh_frame_ptr_encoding, hdr.fde_count_encoding, hdr.table_encoding),
+        (0x1b, 0x03, 0x3b)
+    );
+    assert_eq!(hdr.eh_frame_ptr, res.section(".eh_frame").unwrap().shdr().address());
+    assert_eq!(hdr.table, vec![
+        (0x520, 0x788),
+        (0x540, 0x758),
+        (0x64a, 0x7b0),
+        (0x680, 0x7d0),
+        (0x6f0, 0x818),
+    ]);
+    assert!(hdr.table.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(hdr.lookup(0x64a), Some(0x7b0));
+    assert_eq!(hdr.lookup(0x660), Some(0x7b0));
+    assert_eq!(hdr.lookup(0x100), None);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let hdr = res.eh_frame_hdr().unwrap();
+    assert!(!hdr.table.is_empty());
+    assert!(hdr.table.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(hdr.eh_frame_ptr, res.section(".eh_frame").unwrap().shdr().address());
+
+    // `DW_EH_PE_pcrel` wraps around the address space like the loader does
+    let data = [1, 0x1b, 0x03, 0x3b, 0x10, 0, 0, 0, 0, 0, 0, 0];
+    let hdr = parse_eh_frame_hdr(&data, u64::max_value() - 1, true).unwrap();
+    assert_eq!(hdr.eh_frame_ptr, 0x12);
+    assert!(hdr.table.is_empty());
+
+    assert_eq!(read_leb128(&[0xe5, 0x8e, 0x26], false), Some((624485, 3)));
+    assert_eq!(read_leb128(&[0xc0, 0xbb, 0x78], true), Some((-123456i64 as u64, 3)));
+}
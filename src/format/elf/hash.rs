@@ -0,0 +1,241 @@
+//! Symbol hash tables, the SysV `.hash` (`SHT_HASH`) and the GNU `.gnu.hash` (`SHT_GNU_HASH`).
+//! Both map a symbol name to its index in the linked symbol table (usually `.dynsym`) without
+//! scanning it.
+use format::elf::ElfSymbol;
+use nom::{IResult::*, Needed::{Size, Unknown}, *};
+use error::{ParseCause, RustepError};
+
+/// The SysV ABI hash function used by `.hash`
+pub fn elf_hash(name: &[u8]) -> u32 {
+    let mut h: u32 = 0;
+    for &c in name {
+        h = (h << 4).wrapping_add(c as u32);
+        let g = h & 0xf0000000;
+        if g != 0 {
+            h ^= g >> 24;
+        }
+        h &= !g;
+    }
+
+    h
+}
+
+/// The hash function used by `.gnu.hash` (DJB's `h * 33 + c`)
+pub fn gnu_hash(name: &[u8]) -> u32 {
+    name.iter()
+        .fold(5381u32, |h, &c| h.wrapping_mul(33).wrapping_add(c as u32))
+}
+
+/// A parsed SysV `.hash` section, see `ElfFormat::sysv_hash`.
+pub struct SysvHash<'a> {
+    buckets: Vec<u32>,
+    chains: Vec<u32>,
+    symbols: Vec<&'a ElfSymbol>,
+}
+
+impl<'a> SysvHash<'a> {
+    pub(crate) fn parse(data: &[u8], symbols: Vec<&'a ElfSymbol>) -> Result<SysvHash<'a>, RustepError> {
+        let (buckets, chains) = nom_try!(do_parse!(data,
+            nbucket: le_u32 >>
+            nchain: le_u32 >>
+            buckets: count!(le_u32, nbucket as usize) >>
+            chains: count!(le_u32, nchain as usize) >>
+            (buckets, chains)
+        ), 0, "hash table");
+
+        Ok(SysvHash {
+            buckets: buckets,
+            chains: chains,
+            symbols: symbols,
+        })
+    }
+
+    /// Index of the symbol with the given name in the linked symbol table.
+    pub fn lookup(&self, name: &str) -> Option<usize> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        let mut index = self.buckets[elf_hash(name.as_bytes()) as usize % self.buckets.len()];
+        // A well formed chain ends with `STN_UNDEF`, bound the walk in case it loops
+        for _ in 0..self.chains.len() {
+            if index == 0 {
+                break;
+            }
+            if self.symbols.get(index as usize)?.name() == name {
+                return Some(index as usize);
+            }
+            index = *self.chains.get(index as usize)?;
+        }
+
+        None
+    }
+}
+
+/// A parsed `.gnu.hash` section, see `ElfFormat::gnu_hash`.
+pub struct GnuHash<'a> {
+    /// Index of the first symbol covered by the table, the ones before are not hashed
+    symbol_offset: u32,
+    bloom_shift: u32,
+    /// Bloom filter words, 32 or 64 bits depending on the class
+    bloom: Vec<u64>,
+    bloom_bits: u32,
+    buckets: Vec<u32>,
+    chains: Vec<u32>,
+    symbols: Vec<&'a ElfSymbol>,
+}
+
+impl<'a> GnuHash<'a> {
+    pub(crate) fn parse(
+        data: &[u8],
+        is_64: bool,
+        symbols: Vec<&'a ElfSymbol>
+    ) -> Result<GnuHash<'a>, RustepError> {
+        let (nbuckets, symbol_offset, bloom_size, bloom_shift) = nom_try!(
+            tuple!(data, le_u32, le_u32, le_u32, le_u32),
+            0,
+            "GNU hash header"
+        );
+        // Used as a shift amount of the 32 bits hash in `lookup`
+        if bloom_shift >= 32 {
+            Err(RustepError::Parse {
+                offset: 12,
+                context: "GNU hash header",
+                cause: ParseCause::Invalid,
+            })?
+        }
+        let rest = &data[16..];
+        let (rest, bloom) = if is_64 {
+            let bloom = nom_try!(
+                count!(rest, le_u64, bloom_size as usize),
+                16,
+                "GNU hash bloom filter"
+            );
+            (&rest[bloom.len() * 8..], bloom)
+        } else {
+            let bloom = nom_try!(
+                count!(rest, le_u32, bloom_size as usize),
+                16,
+                "GNU hash bloom filter"
+            );
+            (&rest[bloom.len() * 4..], bloom.iter().map(|&w| w as u64).collect())
+        };
+        let buckets = nom_try!(
+            count!(rest, le_u32, nbuckets as usize),
+            data.len() - rest.len(),
+            "GNU hash buckets"
+        );
+        // The chains run to the end of the section, one per hashed symbol
+        let rest = &rest[buckets.len() * 4..];
+        let chains = nom_try!(
+            count!(rest, le_u32, rest.len() / 4),
+            data.len() - rest.len(),
+            "GNU hash chains"
+        );
+
+        Ok(GnuHash {
+            symbol_offset: symbol_offset,
+            bloom_shift: bloom_shift,
+            bloom: bloom,
+            bloom_bits: if is_64 { 64 } else { 32 },
+            buckets: buckets,
+            chains: chains,
+            symbols: symbols,
+        })
+    }
+
+    /// Index of the symbol with the given name in the linked symbol table.
+    pub fn lookup(&self, name: &str) -> Option<usize> {
+        if self.buckets.is_empty() || self.bloom.is_empty() {
+            return None;
+        }
+        let hash = gnu_hash(name.as_bytes());
+
+        // The bloom filter rejects most missing names before touching the buckets
+        let word = self.bloom[(hash / self.bloom_bits) as usize % self.bloom.len()];
+        let mask = (1u64 << (hash % self.bloom_bits))
+            | (1u64 << ((hash >> self.bloom_shift) % self.bloom_bits));
+        if word & mask != mask {
+            return None;
+        }
+
+        let mut index = self.buckets[hash as usize % self.buckets.len()];
+        if index < self.symbol_offset {
+            return None;
+        }
+        loop {
+            let chain_hash = *self.chains.get((index - self.symbol_offset) as usize)?;
+            // The lowest bit marks the end of the chain, the rest is the symbol's hash
+            if hash | 1 == chain_hash | 1 && self.symbols.get(index as usize)?.name() == name {
+                return Some(index as usize);
+            }
+            if chain_hash & 1 != 0 {
+                return None;
+            }
+            index += 1;
+        }
+    }
+}
+
+#[test]
+fn test_hash_functions() {
+    // Reference values from the ABI documents
+    assert_eq!(elf_hash(b""), 0);
+    assert_eq!(elf_hash(b"printf"), 0x077905a6);
+    assert_eq!(gnu_hash(b""), 0x00001505);
+    assert_eq!(gnu_hash(b"printf"), 0x156b2bb8);
+}
+
+#[test]
+fn test_hash_lookup() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test_hash.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Linked with `--hash-style=both`, both tables must agree with a linear search
+    let sysv = res.sysv_hash().unwrap();
+    let gnu = res.gnu_hash().unwrap();
+    let dynsym = res.dynamic_symbols();
+    for (i, sym) in dynsym.iter().enumerate().skip(1) {
+        assert_eq!(sysv.lookup(sym.name()), Some(i), "{}", sym.name());
+        // Undefined symbols are not part of `.gnu.hash`
+        if sym.section_index() != 0 {
+            assert_eq!(gnu.lookup(sym.name()), Some(i), "{}", sym.name());
+        }
+    }
+    assert!(dynsym.iter().any(|sym| sym.name() == "func_11"));
+    assert_eq!(sysv.lookup("no_such_symbol"), None);
+    assert_eq!(gnu.lookup("no_such_symbol"), None);
+
+    // A bloom shift wider than the hash is rejected
+    let offset = res.section(".gnu.hash").unwrap().shdr().offset() as usize;
+    let mut patched = buf.clone();
+    patched[offset + 12..offset + 16].copy_from_slice(&40u32.to_le_bytes());
+    let result = parse_elf(&patched).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.gnu_hash().is_none());
+    let sec = res.section(".gnu.hash").unwrap();
+    match GnuHash::parse(sec.data(), true, Vec::new()) {
+        Err(RustepError::Parse { offset, context, .. }) => {
+            assert_eq!((offset, context), (12, "GNU hash header"))
+        },
+        _ => panic!("bloom shift of 40 accepted"),
+    }
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.sysv_hash().is_none());
+    let gnu = res.gnu_hash().unwrap();
+    // Exported by every PIE, checked with `readelf --dyn-syms`
+    let index = res.dynamic_symbols().iter().position(|s| s.name() == "_IO_stdin_used").unwrap();
+    assert_eq!(gnu.lookup("_IO_stdin_used"), Some(index));
+    assert_eq!(gnu.lookup("printf"), None);
+}
@@ -0,0 +1,174 @@
+//! The MIPS specific `.MIPS.abiflags` and `.reginfo` sections, which tell the ABI a file was
+//! built for, see `ElfFormat::mips_abi_flags` and `ElfFormat::mips_reginfo`.
+use format::elf::{MaybeKnown, ReservedRanges};
+use nom::*;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Size of a register class, the `AFL_REG_*` values
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum MipsRegSize {
+    /// The registers are not used
+    AFL_REG_NONE = 0,
+    AFL_REG_32 = 1,
+    AFL_REG_64 = 2,
+    AFL_REG_128 = 3,
+}
+
+impl ReservedRanges for MipsRegSize {}
+
+impl MipsRegSize {
+    /// Size in bits, `0` for `AFL_REG_NONE`.
+    pub fn bits(&self) -> u32 {
+        match *self {
+            MipsRegSize::AFL_REG_NONE => 0,
+            MipsRegSize::AFL_REG_32 => 32,
+            MipsRegSize::AFL_REG_64 => 64,
+            MipsRegSize::AFL_REG_128 => 128,
+        }
+    }
+}
+
+/// Floating point ABI, the `Val_GNU_MIPS_ABI_FP_*` values
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum MipsFpAbi {
+    /// No floating point used
+    FP_ANY = 0,
+    /// Hard float, double precision
+    FP_DOUBLE = 1,
+    /// Hard float, single precision
+    FP_SINGLE = 2,
+    FP_SOFT = 3,
+    /// 64-bit FPU registers, deprecated
+    FP_OLD_64 = 4,
+    /// Works with either FPU register size
+    FP_XX = 5,
+    /// 64-bit FPU registers
+    FP_64 = 6,
+    /// 64-bit FPU registers, without odd single precision registers
+    FP_64A = 7,
+}
+
+impl ReservedRanges for MipsFpAbi {}
+
+/// The `Elf_MIPS_ABIFlags_v0` structure of `.MIPS.abiflags`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MipsAbiFlags {
+    /// Version of the structure, always 0
+    pub version: u16,
+    /// ISA level, e.g. 32 for MIPS32 or 64 for MIPS64
+    pub isa_level: u8,
+    /// ISA revision, e.g. 2 for MIPS32r2
+    pub isa_rev: u8,
+    /// Size of the general purpose registers
+    pub gpr_size: MaybeKnown<MipsRegSize>,
+    /// Size of the floating point registers (coprocessor 1)
+    pub cpr1_size: MaybeKnown<MipsRegSize>,
+    /// Size of the coprocessor 2 registers
+    pub cpr2_size: MaybeKnown<MipsRegSize>,
+    pub fp_abi: MaybeKnown<MipsFpAbi>,
+    /// Processor specific extension, `AFL_EXT_*`
+    pub isa_ext: u32,
+    /// Application specific extensions used, `AFL_ASE_*` bits
+    pub ases: u32,
+    /// `AFL_FLAGS1_*` bits, e.g. `AFL_FLAGS1_ODDSPREG` (1)
+    pub flags1: u32,
+    pub flags2: u32,
+}
+
+/// The `Elf32_RegInfo` structure of `.reginfo`, the registers used by the file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MipsRegInfo {
+    /// Bit `n` is set when general purpose register `n` is used
+    pub gpr_mask: u32,
+    /// Same as `gpr_mask` for the registers of coprocessors 0 to 3
+    pub cpr_mask: [u32; 4],
+    /// Value of the global pointer (`$gp`)
+    pub gp_value: i32,
+}
+
+named!(pub parse_abiflags<&[u8], MipsAbiFlags>,
+    do_parse!(
+        version: le_u16 >>
+        isa_level: le_u8 >>
+        isa_rev: le_u8 >>
+        gpr_size: le_u8 >>
+        cpr1_size: le_u8 >>
+        cpr2_size: le_u8 >>
+        fp_abi: le_u8 >>
+        isa_ext: le_u32 >>
+        ases: le_u32 >>
+        flags1: le_u32 >>
+        flags2: le_u32 >>
+        (MipsAbiFlags {
+            version: version,
+            isa_level: isa_level,
+            isa_rev: isa_rev,
+            gpr_size: MaybeKnown::from_u32(gpr_size as u32),
+            cpr1_size: MaybeKnown::from_u32(cpr1_size as u32),
+            cpr2_size: MaybeKnown::from_u32(cpr2_size as u32),
+            fp_abi: MaybeKnown::from_u32(fp_abi as u32),
+            isa_ext: isa_ext,
+            ases: ases,
+            flags1: flags1,
+            flags2: flags2,
+        })
+    )
+);
+
+named!(pub parse_reginfo<&[u8], MipsRegInfo>,
+    do_parse!(
+        gpr_mask: le_u32 >>
+        cpr0_mask: le_u32 >>
+        cpr1_mask: le_u32 >>
+        cpr2_mask: le_u32 >>
+        cpr3_mask: le_u32 >>
+        gp_value: le_i32 >>
+        (MipsRegInfo {
+            gpr_mask: gpr_mask,
+            cpr_mask: [cpr0_mask, cpr1_mask, cpr2_mask, cpr3_mask],
+            gp_value: gp_value,
+        })
+    )
+);
+
+#[test]
+fn test_mips_sections() {
+    use std::{convert::TryInto, fs::File, io::prelude::*};
+    use format::elf::{parse_elf, ElfFormat};
+
+    // Assembled with `llvm-mc -triple=mipsel-linux-gnu`, checked with `readelf -A`
+    let mut file = File::open("test/test_mips.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let flags = res.mips_abi_flags().unwrap();
+    assert_eq!((flags.version, flags.isa_level, flags.isa_rev), (0, 32, 1));
+    assert!(flags.gpr_size == MipsRegSize::AFL_REG_32);
+    assert_eq!(flags.cpr1_size.known().map(|size| size.bits()), Some(32));
+    assert!(flags.cpr2_size == MipsRegSize::AFL_REG_NONE);
+    assert!(flags.fp_abi == MipsFpAbi::FP_DOUBLE);
+    assert_eq!((flags.isa_ext, flags.ases, flags.flags1, flags.flags2), (0, 0, 1, 0));
+
+    // `$zero` (0), `$v0` (2) and `$ra` (31), the only registers the code touches
+    let reginfo = res.mips_reginfo().unwrap();
+    assert_eq!(reginfo.gpr_mask, 1 << 31 | 1 << 2 | 1);
+    assert_eq!(reginfo.cpr_mask, [0; 4]);
+    assert_eq!(reginfo.gp_value, 0);
+
+    // Not looked for on other machines
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.mips_abi_flags().is_none());
+    assert!(res.mips_reginfo().is_none());
+}
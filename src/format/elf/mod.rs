@@ -0,0 +1,6785 @@
+//! Definition of overall elf file format and Reexports bindings as low level implementation 
+//! of elf file format for it has a complete header already described the file format 
+//! structure overall.
+use format::bindings::*;
+use std::{
+    any::Any,
+    borrow::Cow,
+    cmp,
+    collections::HashMap,
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    mem,
+    convert::{
+        TryFrom,
+        TryInto,
+    },
+};
+use nom::{IResult, IResult::*, Needed::{Size, Unknown}, *};
+use error::{ParseCause, RustepError};
+use format::binary::Arch;
+use format::executable::{
+    Executable,
+    ParseOptions,
+};
+use num_traits::FromPrimitive;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use format::serialize::ElfFormatSerializer;
+
+mod symbol;
+mod relocation;
+mod reloc;
+mod dynamic;
+mod hash;
+mod string_table;
+mod builder;
+mod note;
+mod coredump;
+mod eh_frame;
+mod address_index;
+mod mips;
+
+pub use self::symbol::{
+    ElfSymbol,
+    ElfSymbol32,
+    ElfSymbol64,
+    SHN_ABS,
+    SHN_COMMON,
+    SHN_UNDEF,
+    SymbolBinding,
+    SymbolType,
+};
+pub use self::relocation::{
+    ElfRelocation,
+    GotEntry,
+    PltEntry,
+    ResolvedReloc,
+};
+pub use self::reloc::{
+    reloc_type_name,
+    RelocAArch64,
+    RelocArm,
+    RelocI386,
+    RelocX86_64,
+};
+pub use self::hash::{
+    elf_hash,
+    gnu_hash,
+    GnuHash,
+    SysvHash,
+};
+pub use self::string_table::StringTable;
+pub use format::bindings::{Elf32_Ehdr, Elf64_Ehdr};
+pub use self::address_index::AddressIndex;
+pub use self::builder::ElfBuilder;
+pub use self::note::{
+    Note,
+    NoteOrigin,
+};
+pub use self::eh_frame::{
+    EhFrameHdr,
+    DW_EH_PE_OMIT,
+};
+pub use self::mips::{
+    MipsAbiFlags,
+    MipsFpAbi,
+    MipsRegInfo,
+    MipsRegSize,
+};
+pub use self::coredump::{
+    MappedFile,
+    ProcessInfo,
+    Registers,
+    X86_64Registers,
+};
+pub use self::dynamic::{
+    decode_dynamic_flags,
+    decode_dynamic_flags_1,
+    DynamicEntry,
+    DynamicFlag,
+    DynamicFlag1,
+    DynamicTag,
+    DF_1_PIE,
+};
+use self::symbol::{
+    parse_elf_symbol32,
+    parse_elf_symbol64,
+};
+use self::relocation::{
+    parse_relocations32,
+    parse_relocations64,
+};
+use self::dynamic::{
+    parse_dynamic32,
+    parse_dynamic64,
+};
+use self::note::parse_notes;
+use self::eh_frame::parse_eh_frame_hdr;
+use self::mips::{parse_abiflags, parse_reginfo};
+use self::string_table::IndexedStringTable;
+use self::coredump::{
+    parse_file_note,
+    parse_prpsinfo,
+    parse_x86_64_prstatus,
+};
+
+/// Elf types, refer to `ELF`'s `e_type`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ElfType {
+     ET_REL = 1,
+     ET_EXEC = 2,
+     ET_DYN = 3,
+     ET_CORE = 4,
+     ET_NUM = 5,
+     ET_LOOS = 65024,
+     ET_HIOS = 65279,
+     ET_LOPROC = 65280,
+     ET_HIPROC = 65535,
+}
+
+/// A type value read from the file that is either one of the values we know about, or a raw
+/// value we are not able to resolve (vendor or OS specific ones like `SHT_ARM_EXIDX`). Unknown
+/// values are kept instead of failing the whole parse.
+///
+/// It can be compared against the known enum directly, e.g.
+/// `*section.section_type() == SectionType::SHT_SYMTAB`.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum MaybeKnown<T> {
+    Known(T),
+    Unknown(u32),
+}
+
+impl<T: FromPrimitive> MaybeKnown<T> {
+    /// Resolves a raw value, falling back to `Unknown` if it is not a known one.
+    pub fn from_u32(value: u32) -> MaybeKnown<T> {
+        match FromPrimitive::from_u32(value) {
+            Some(known) => MaybeKnown::Known(known),
+            None => MaybeKnown::Unknown(value),
+        }
+    }
+}
+
+impl<T> MaybeKnown<T> {
+    /// The resolved value, `None` if unknown.
+    pub fn known(&self) -> Option<&T> {
+        match *self {
+            MaybeKnown::Known(ref known) => Some(known),
+            MaybeKnown::Unknown(_) => None,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for MaybeKnown<T> {
+    fn eq(&self, other: &T) -> bool {
+        match *self {
+            MaybeKnown::Known(ref known) => known == other,
+            MaybeKnown::Unknown(_) => false,
+        }
+    }
+}
+
+impl<T: fmt::Debug + ReservedRanges> fmt::Display for MaybeKnown<T> {
+    /// Known values are rendered with their symbolic name, unknown ones relative to the
+    /// reserved range they fall in, e.g. `PT_LOPROC+0x1`, or as hex.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MaybeKnown::Known(ref known) => write!(f, "{:?}", known),
+            MaybeKnown::Unknown(value) => {
+                match T::RANGES.iter().find(|&&(start, end, _)| value >= start && value <= end) {
+                    Some(&(start, _, name)) => write!(f, "{}+{:#x}", name, value - start),
+                    None => write!(f, "{:#x}", value),
+                }
+            },
+        }
+    }
+}
+
+/// Ranges of values reserved for operating system or processor specific use, for rendering the
+/// values of these ranges we do not know about.
+pub trait ReservedRanges {
+    /// First value, last value and name of each range
+    const RANGES: &'static [(u32, u32, &'static str)] = &[];
+}
+
+impl ReservedRanges for SegmentType {
+    const RANGES: &'static [(u32, u32, &'static str)] = &[
+        (0x60000000, 0x6fffffff, "PT_LOOS"),
+        (0x70000000, 0x7fffffff, "PT_LOPROC"),
+    ];
+}
+
+impl ReservedRanges for SectionType {
+    const RANGES: &'static [(u32, u32, &'static str)] = &[
+        (0x60000000, 0x6fffffff, "SHT_LOOS"),
+        (0x70000000, 0x7fffffff, "SHT_LOPROC"),
+        (0x80000000, 0xffffffff, "SHT_LOUSER"),
+    ];
+}
+
+impl fmt::Display for SegmentType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl fmt::Display for SectionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Elf segment type, refer to `segment`'s `p_type`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SegmentType {
+    PT_NULL = 0,
+    PT_LOAD = 1,
+    PT_DYNAMIC = 2,
+    PT_INTERP = 3,
+    PT_NOTE = 4,
+    PT_SHLIB = 5,
+    PT_PHDR = 6,
+    PT_TLS = 7,
+    PT_NUM = 8,
+    PT_LOOS = 1610612736,
+    PT_GNU_EH_FRAME = 1685382480,
+    PT_GNU_STACK = 1685382481,
+    PT_GNU_RELRO = 1685382482,
+    PT_LOSUNW = 1879048186,
+    //PT_SUNWBSS = 1879048186,
+    PT_SUNWSTACK = 1879048187,
+    PT_HISUNW = 1879048191,
+    //PT_HIOS = 1879048191,
+    PT_LOPROC = 1879048192,
+    PT_HIPROC = 2147483647,
+}
+
+bitflags! {
+    /// Elf segment's flag. Refer to `segment`'s `p_flags`
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct SegmentFlag: u64 {
+        const PF_X = 1;
+        const PF_W = 2;
+        const PF_R = 4;
+        const PF_MASKOS = 267386880;
+        const PF_MASKPROC = 4026531840;
+    }
+}
+
+/// Elf section's type referring to `section`'s `sh_type`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SectionType {
+     SHT_NULL = 0,
+     SHT_PROGBITS = 1,
+     SHT_SYMTAB = 2,
+     SHT_STRTAB = 3,
+     SHT_RELA = 4,
+     SHT_HASH = 5,
+     SHT_DYNAMIC = 6,
+     SHT_NOTE = 7,
+     SHT_NOBITS = 8,
+     SHT_REL = 9,
+     SHT_SHLIB = 10,
+     SHT_DYNSYM = 11,
+     SHT_INIT_ARRAY = 14,
+     SHT_FINI_ARRAY = 15,
+     SHT_PREINIT_ARRAY = 16,
+     SHT_GROUP = 17,
+     SHT_SYMTAB_SHNDX = 18,
+     SHT_NUM = 19,
+     SHT_LOOS = 1610612736,
+     SHT_GNU_ATTRIBUTES = 1879048181,
+     SHT_GNU_HASH = 1879048182,
+     SHT_GNU_LIBLIST = 1879048183,
+     SHT_CHECKSUM = 1879048184,
+     SHT_LOSUNW = 1879048186,
+     //SHT_SUNW_move = 1879048186,
+     SHT_SUNW_COMDAT = 1879048187,
+     SHT_SUNW_syminfo = 1879048188,
+     SHT_GNU_verdef = 1879048189,
+     SHT_GNU_verneed = 1879048190,
+     SHT_GNU_versym = 1879048191,
+     //SHT_HISUNW = 1879048191,
+     //SHT_HIOS = 1879048191,
+     SHT_LOPROC = 1879048192,
+     SHT_HIPROC = 2147483647,
+     SHT_LOUSER = 2147483648,
+     SHT_HIUSER = 2415919103,
+}
+
+bitflags! {
+    /// Elf section's type referring to `section`'s `sh_flags`
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct SectionFlag: u64 {
+        const SHF_WRITE = 1;
+        const SHF_ALLOC = 2;
+        const SHF_EXECINSTR = 4;
+        const SHF_MERGE = 16;
+        const SHF_STRINGS = 32;
+        const SHF_INFO_LINK = 64;
+        const SHF_LINK_ORDER = 128;
+        const SHF_OS_NONCONFORMING = 256;
+        const SHF_GROUP = 512;
+        const SHF_TLS = 1024;
+        const SHF_COMPRESSED = 2048;
+        const SHF_MASKOS = 267386880;
+        //const SHF_MASKPROC = 4026531840;
+        //const SHF_ORDERED = 1073741824;
+        //const SHF_EXCLUDE = 2147483648;
+    }
+}
+
+/// Renders segment flags the way `readelf -l` does, e.g. `R E` for `PF_R | PF_X`.
+pub fn segment_flags_short(flags: SegmentFlag) -> String {
+    let mut s = String::with_capacity(3);
+    s.push(if flags.contains(SegmentFlag::PF_R) { 'R' } else { ' ' });
+    s.push(if flags.contains(SegmentFlag::PF_W) { 'W' } else { ' ' });
+    s.push(if flags.contains(SegmentFlag::PF_X) { 'E' } else { ' ' });
+    s
+}
+
+/// Renders section flags as the short letters used by `readelf -S`, e.g. `AX` for
+/// `SHF_ALLOC | SHF_EXECINSTR`.
+pub fn section_flags_short(flags: SectionFlag) -> String {
+    let letters = [
+        (SectionFlag::SHF_WRITE, 'W'),
+        (SectionFlag::SHF_ALLOC, 'A'),
+        (SectionFlag::SHF_EXECINSTR, 'X'),
+        (SectionFlag::SHF_MERGE, 'M'),
+        (SectionFlag::SHF_STRINGS, 'S'),
+        (SectionFlag::SHF_INFO_LINK, 'I'),
+        (SectionFlag::SHF_LINK_ORDER, 'L'),
+        (SectionFlag::SHF_OS_NONCONFORMING, 'O'),
+        (SectionFlag::SHF_GROUP, 'G'),
+        (SectionFlag::SHF_TLS, 'T'),
+        (SectionFlag::SHF_COMPRESSED, 'C'),
+    ];
+    let mut s = String::new();
+    for &(flag, letter) in letters.iter() {
+        if flags.contains(flag) {
+            s.push(letter);
+        }
+    }
+    if flags.intersects(SectionFlag::SHF_MASKOS) {
+        s.push('o');
+    }
+    s
+}
+
+/// A trait representing the supported methods for a extracted section header.
+/// This is used as universal interface for ElfXX_Shdr, since those are generated from C header,
+/// some methods are useful when using those ignoring the 32 or 64 part.
+pub trait ElfSectionHeader {
+    fn address(&self) -> u64;
+    fn offset(&self) -> u64;
+    fn size(&self) -> u64;
+    fn entry_size(&self) -> u64;
+    /// `sh_link`, index of an associated section, e.g. the symbol table used by a relocation
+    /// section or the string table used by a symbol table
+    fn link(&self) -> u64;
+    /// `sh_info`, meaning depends on the type, e.g. the section a relocation section applies to
+    /// or one past the last local symbol of a symbol table
+    fn info(&self) -> u64;
+    /// `sh_addralign`, `0` and `1` both meaning no constraint
+    fn addr_align(&self) -> u64;
+    /// `sh_name`, offset of the name in the section header string table
+    fn name_offset(&self) -> u32;
+    /// Encodes the section header back into its on-disk little endian layout.
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl ElfSectionHeader for Elf32_Shdr {
+    fn address(&self) -> u64 {
+        self.sh_addr as u64
+    }
+
+    fn offset(&self) -> u64 {
+        self.sh_offset as u64
+    }
+
+    fn size(&self) -> u64 {
+        self.sh_size as u64
+    }
+    
+    fn entry_size(&self) -> u64 {
+        self.sh_entsize as u64
+    }
+
+    fn link(&self) -> u64 {
+        self.sh_link as u64
+    }
+
+    fn info(&self) -> u64 {
+        self.sh_info as u64
+    }
+
+    fn addr_align(&self) -> u64 {
+        self.sh_addralign as u64
+    }
+
+    fn name_offset(&self) -> u32 {
+        self.sh_name
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(mem::size_of::<Elf32_Shdr>());
+        bytes.extend_from_slice(&self.sh_name.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_type.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_flags.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_addr.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_offset.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_size.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_link.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_info.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_addralign.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_entsize.to_le_bytes());
+
+        bytes
+    }
+}
+
+impl ElfSectionHeader for Elf64_Shdr {
+    fn address(&self) -> u64 {
+        self.sh_addr
+    }
+
+    fn offset(&self) -> u64 {
+        self.sh_offset
+    }
+
+    fn size(&self) -> u64 {
+        self.sh_size
+    }
+
+    fn entry_size(&self) -> u64 {
+        self.sh_entsize
+    }
+
+    fn link(&self) -> u64 {
+        self.sh_link as u64
+    }
+
+    fn info(&self) -> u64 {
+        self.sh_info as u64
+    }
+
+    fn addr_align(&self) -> u64 {
+        self.sh_addralign
+    }
+
+    fn name_offset(&self) -> u32 {
+        self.sh_name
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(mem::size_of::<Elf64_Shdr>());
+        bytes.extend_from_slice(&self.sh_name.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_type.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_flags.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_addr.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_offset.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_size.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_link.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_info.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_addralign.to_le_bytes());
+        bytes.extend_from_slice(&self.sh_entsize.to_le_bytes());
+
+        bytes
+    }
+}
+
+/// A trait to provide all functions supported by ElfSectionXX structure representation.
+/// Dynamic dispatch is used to provide only function usages, thus 32-bit and 64-bit can be used
+/// alike.
+pub trait ElfSection {
+    /// Internal shdr representation of this section. Note that since dynamic dispatch is used,
+    /// this method mostly just provide the functionalities, not the full struct.
+    fn shdr(&self) -> &ElfSectionHeader;
+    /// Name of this section.
+    fn name(&self) -> &str;
+    /// Index of this section in the section header table, the one `sh_link`, `sh_info` and
+    /// `st_shndx` refer to.
+    fn index(&self) -> usize;
+    /// Type of this section, unknown types keep their raw value
+    fn section_type(&self) -> &MaybeKnown<SectionType>;
+    /// Flags of this section. Bits not covered by `SectionFlag` are kept, `iter_names` only
+    /// gives the known ones.
+    fn flags(&self) -> SectionFlag;
+    /// Raw `sh_flags` value, including OS or processor specific bits
+    fn raw_flags(&self) -> u64;
+    /// Data of this section
+    fn data(&self) -> &[u8];
+    /// Whether the section occupies memory at run time (`SHF_ALLOC`)
+    fn is_alloc(&self) -> bool {
+        self.flags().contains(SectionFlag::SHF_ALLOC)
+    }
+    /// Whether the section is writable at run time (`SHF_WRITE`)
+    fn is_writable(&self) -> bool {
+        self.flags().contains(SectionFlag::SHF_WRITE)
+    }
+    /// Whether the section holds executable instructions (`SHF_EXECINSTR`)
+    fn is_executable(&self) -> bool {
+        self.flags().contains(SectionFlag::SHF_EXECINSTR)
+    }
+    /// Whether the section holds thread-local storage (`SHF_TLS`)
+    fn is_tls(&self) -> bool {
+        self.flags().contains(SectionFlag::SHF_TLS)
+    }
+}
+
+/// Sections are equal when their names and headers are. The contents are not compared, they
+/// are described by the headers when both sections come from the same file or from copies.
+impl<'a> PartialEq for ElfSection + 'a {
+    fn eq(&self, other: &(ElfSection + 'a)) -> bool {
+        self.name() == other.name() && self.shdr().to_bytes() == other.shdr().to_bytes()
+    }
+}
+
+impl<'a> Eq for ElfSection + 'a {}
+
+impl<'a> Hash for ElfSection + 'a {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+        self.shdr().to_bytes().hash(state);
+    }
+}
+
+/// 32-bit Elf Section representation
+pub struct ElfSection32<'a> {
+    /// Internal Shdr. If you only need the functionality provided, just use the getter.
+    shdr: Elf32_Shdr,
+    section_type: MaybeKnown<SectionType>,
+    flags: SectionFlag,
+    name: String,
+    index: usize,
+    data: Cow<'a, [u8]>,
+}
+
+/// 64-bit ElfSection representation
+pub struct ElfSection64<'a> {
+    /// Internal Shdr. If you only need the functionality provided, just use the getter.
+    shdr: Elf64_Shdr,
+    section_type: MaybeKnown<SectionType>,
+    flags: SectionFlag,
+    name: String,
+    index: usize,
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> ElfSection for ElfSection32<'a> {
+    fn shdr(&self) -> &ElfSectionHeader {
+        &self.shdr
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn section_type(&self) -> &MaybeKnown<SectionType> {
+        &self.section_type
+    }
+
+    fn flags(&self) -> SectionFlag {
+        self.flags
+    }
+
+    fn raw_flags(&self) -> u64 {
+        self.shdr.sh_flags as u64
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> ElfSection for ElfSection64<'a> {
+    fn shdr(&self) -> &ElfSectionHeader {
+        &self.shdr
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn section_type(&self) -> &MaybeKnown<SectionType> {
+        &self.section_type
+    }
+
+    fn flags(&self) -> SectionFlag {
+        self.flags
+    }
+
+    fn raw_flags(&self) -> u64 {
+        self.shdr.sh_flags as u64
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> fmt::Display for ElfSection + 'a {
+    /// One line summary: name, type, flags, address and size.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} [{}] address: {:#x} size: {:#x}",
+            self.name(),
+            self.section_type(),
+            section_flags_short(self.flags()),
+            self.shdr().address(),
+            self.shdr().size()
+        )
+    }
+}
+
+impl<'a> ElfSection + 'a {
+    /// Splits the section into `sh_entsize` sized entries and parses each of them, e.g.
+    /// `sec.entries::<ElfSymbol64>()` for `.dynsym` or `sec.entries::<u64>()` for
+    /// `.init_array`. `sh_entsize` must be the size of `T` in the file, and the section made of
+    /// whole entries.
+    pub fn entries<T: FromBytes>(&self) -> Result<Vec<T>, RustepError> {
+        let entry_size = self.shdr().entry_size();
+        if entry_size != T::SIZE as u64 {
+            Err(RustepError::SizeMismatch(T::SIZE as u64, entry_size))?
+        }
+        let data = self.data();
+        if data.len() % T::SIZE != 0 {
+            Err(RustepError::PartialEntry { size: data.len() as u64, entry_size: entry_size })?
+        }
+
+        data.chunks(T::SIZE)
+            .enumerate()
+            .map(|(i, chunk)| T::from_bytes(chunk).ok_or(RustepError::Parse {
+                offset: i * T::SIZE,
+                context: "section entry",
+                cause: ParseCause::Invalid,
+            }))
+            .collect()
+    }
+}
+
+/// A fixed size entry of a table section, see `ElfSection::entries`. Like the rest of the
+/// parser, values are read as little endian.
+pub trait FromBytes: Sized {
+    /// Size of an entry in the file
+    const SIZE: usize;
+    /// Parses an entry from exactly `SIZE` bytes
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+impl FromBytes for u16 {
+    const SIZE: usize = 2;
+    fn from_bytes(bytes: &[u8]) -> Option<u16> {
+        le_u16(bytes).to_result().ok()
+    }
+}
+
+impl FromBytes for u32 {
+    const SIZE: usize = 4;
+    fn from_bytes(bytes: &[u8]) -> Option<u32> {
+        le_u32(bytes).to_result().ok()
+    }
+}
+
+impl FromBytes for u64 {
+    const SIZE: usize = 8;
+    fn from_bytes(bytes: &[u8]) -> Option<u64> {
+        le_u64(bytes).to_result().ok()
+    }
+}
+
+/// A trait representing the supported methods for a extracted program header.
+/// This is used as universal interface for ElfXX_Phdr, since those are generated from C header,
+/// some methods are useful when using those ignoring the 32 or 64 part.
+pub trait ElfSegmentHeader {
+    fn offset(&self) -> u64;
+    fn vaddr(&self) -> u64;
+    fn paddr(&self) -> u64;
+    fn file_size(&self) -> u64;
+    fn mem_size(&self) -> u64;
+    /// `p_align`, `0` and `1` both meaning no constraint. Loadable segments must have
+    /// `p_vaddr` congruent to `p_offset` modulo this value.
+    fn align(&self) -> u64;
+    /// Encodes the program header back into its on-disk little endian layout. Note that the
+    /// field order differs between classes, `p_flags` comes second in `Elf64_Phdr` and seventh in
+    /// `Elf32_Phdr`.
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl ElfSegmentHeader for Elf32_Phdr {
+    fn offset(&self) -> u64 {
+        self.p_offset as u64
+    }
+
+    fn vaddr(&self) -> u64 {
+        self.p_vaddr as u64
+    }
+
+    fn paddr(&self) -> u64 {
+        self.p_paddr as u64
+    }
+
+    fn file_size(&self) -> u64 {
+        self.p_filesz as u64
+    }
+
+    fn mem_size(&self) -> u64 {
+        self.p_memsz as u64
+    }
+
+    fn align(&self) -> u64 {
+        self.p_align as u64
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(mem::size_of::<Elf32_Phdr>());
+        bytes.extend_from_slice(&self.p_type.to_le_bytes());
+        bytes.extend_from_slice(&self.p_offset.to_le_bytes());
+        bytes.extend_from_slice(&self.p_vaddr.to_le_bytes());
+        bytes.extend_from_slice(&self.p_paddr.to_le_bytes());
+        bytes.extend_from_slice(&self.p_filesz.to_le_bytes());
+        bytes.extend_from_slice(&self.p_memsz.to_le_bytes());
+        bytes.extend_from_slice(&self.p_flags.to_le_bytes());
+        bytes.extend_from_slice(&self.p_align.to_le_bytes());
+
+        bytes
+    }
+}
+
+impl ElfSegmentHeader for Elf64_Phdr {
+    fn offset(&self) -> u64 {
+        self.p_offset
+    }
+
+    fn vaddr(&self) -> u64 {
+        self.p_vaddr
+    }
+
+    fn paddr(&self) -> u64 {
+        self.p_paddr
+    }
+
+    fn file_size(&self) -> u64 {
+        self.p_filesz
+    }
+
+    fn mem_size(&self) -> u64 {
+        self.p_memsz
+    }
+
+    fn align(&self) -> u64 {
+        self.p_align
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(mem::size_of::<Elf64_Phdr>());
+        bytes.extend_from_slice(&self.p_type.to_le_bytes());
+        bytes.extend_from_slice(&self.p_flags.to_le_bytes());
+        bytes.extend_from_slice(&self.p_offset.to_le_bytes());
+        bytes.extend_from_slice(&self.p_vaddr.to_le_bytes());
+        bytes.extend_from_slice(&self.p_paddr.to_le_bytes());
+        bytes.extend_from_slice(&self.p_filesz.to_le_bytes());
+        bytes.extend_from_slice(&self.p_memsz.to_le_bytes());
+        bytes.extend_from_slice(&self.p_align.to_le_bytes());
+
+        bytes
+    }
+}
+
+/// providing universal functionality of `Elf` segment
+pub trait ElfSegment {
+    /// internal phdr access, note that this method only provide functionalities, since it
+    /// uses dynamic dispatch.
+    fn phdr(&self) -> &ElfSegmentHeader;
+    /// Type of this segment, unknown types keep their raw value
+    fn segment_type(&self) -> &MaybeKnown<SegmentType>;
+    /// Flags of this segment. Bits not covered by `SegmentFlag` are kept, `iter_names` only
+    /// gives the known ones.
+    fn flags(&self) -> SegmentFlag;
+    /// Raw `p_flags` value, including OS or processor specific bits
+    fn raw_flags(&self) -> u64;
+    /// Data of this segment
+    fn data(&self) -> &[u8];
+    /// Whether the segment is mapped executable (`PF_X`)
+    fn is_executable(&self) -> bool {
+        self.flags().contains(SegmentFlag::PF_X)
+    }
+    /// Whether the segment is mapped writable (`PF_W`)
+    fn is_writable(&self) -> bool {
+        self.flags().contains(SegmentFlag::PF_W)
+    }
+    /// Whether the segment is mapped readable (`PF_R`)
+    fn is_readable(&self) -> bool {
+        self.flags().contains(SegmentFlag::PF_R)
+    }
+}
+
+/// Segments are equal when their headers are, see the equality of `ElfSection`.
+impl<'a> PartialEq for ElfSegment + 'a {
+    fn eq(&self, other: &(ElfSegment + 'a)) -> bool {
+        self.phdr().to_bytes() == other.phdr().to_bytes()
+    }
+}
+
+impl<'a> Eq for ElfSegment + 'a {}
+
+impl<'a> Hash for ElfSegment + 'a {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.phdr().to_bytes().hash(state);
+    }
+}
+
+/// The concrete section and segment types compare like their trait objects.
+macro_rules! define_header_eq {
+    ($wrapper: ident, $base: ident) => {
+        impl<'a> PartialEq for $wrapper<'a> {
+            fn eq(&self, other: &$wrapper<'a>) -> bool {
+                (self as &$base) == (other as &$base)
+            }
+        }
+
+        impl<'a> Eq for $wrapper<'a> {}
+
+        impl<'a> Hash for $wrapper<'a> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                (self as &$base).hash(state)
+            }
+        }
+    }
+}
+
+define_header_eq!(ElfSection32, ElfSection);
+define_header_eq!(ElfSection64, ElfSection);
+define_header_eq!(ElfSegment32, ElfSegment);
+define_header_eq!(ElfSegment64, ElfSegment);
+
+/// 32-bit version Elf Segment representation.
+pub struct ElfSegment32<'a> {
+    /// Internal phdr of the segment, full struct
+    phdr: Elf32_Phdr,
+    segment_type: MaybeKnown<SegmentType>,
+    flags: SegmentFlag,
+    data: Cow<'a, [u8]>,
+}
+
+/// 64-bit version Elf Segment representation
+pub struct ElfSegment64<'a> {
+    /// Internal phdr of the segment, full struct
+    phdr: Elf64_Phdr,
+    segment_type: MaybeKnown<SegmentType>,
+    flags: SegmentFlag,
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> ElfSegment for ElfSegment32<'a> {
+    fn phdr(&self) -> &ElfSegmentHeader {
+        &self.phdr
+    }
+
+    fn segment_type(&self) -> &MaybeKnown<SegmentType> {
+        &self.segment_type
+    }
+
+    fn flags(&self) -> SegmentFlag {
+        self.flags
+    }
+
+    fn raw_flags(&self) -> u64 {
+        self.phdr.p_flags as u64
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> ElfSegment for ElfSegment64<'a> {
+    fn phdr(&self) -> &ElfSegmentHeader {
+        &self.phdr
+    }
+
+    fn segment_type(&self) -> &MaybeKnown<SegmentType> {
+        &self.segment_type
+    }
+
+    fn flags(&self) -> SegmentFlag {
+        self.flags
+    }
+
+    fn raw_flags(&self) -> u64 {
+        self.phdr.p_flags as u64
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a> fmt::Display for ElfSegment + 'a {
+    /// One line summary: type, flags, virtual address and size.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] address: {:#x} file size: {:#x} memory size: {:#x}",
+            self.segment_type(),
+            segment_flags_short(self.flags()),
+            self.phdr().vaddr(),
+            self.phdr().file_size(),
+            self.phdr().mem_size()
+        )
+    }
+}
+
+/// Elf machine type, referring to `e_machine` in `ELF` header
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[repr(u64)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ElfMachine {
+    /// Nomachine
+    NONE = 0,
+    /// AT&T WE32100
+    M32 = 1,
+    /// SPARC
+    SPARC = 2,
+    /// Intel80386
+    I386 = 3,
+    /// Motorola68000
+    M68K = 4,
+    /// Motorola88000
+    M88K = 5,
+    /// Intel80860
+    I860 = 7,
+    /// MIPS IArchitecture
+    MIPS = 8,
+    /// IBM System/370Processor
+    S370 = 9,
+    /// MIPS RS3000Little-endian
+    MIPS_RS3_LE = 10,
+    /// Hewlett-PackardPA-RISC
+    PARISC = 15,
+    /// FujitsuVPP500
+    VPP500 = 17,
+    /// Enhanced instruction setSPARC
+    SPARC32PLUS = 18,
+    /// Intel80960
+    I960 = 19,
+    /// PowerPC
+    PPC = 20,
+    /// 64-bitPowerPC
+    PPC64 = 21,
+    /// IBM System/390Processor
+    S390 = 22,
+    /// NECV800
+    V800 = 36,
+    /// FujitsuFR20
+    FR20 = 37,
+    /// TRWRH-32
+    RH32 = 38,
+    /// MotorolaRCE
+    RCE = 39,
+    /// Advanced RISC MachinesARM
+    ARM = 40,
+    /// DigitalAlpha
+    ALPHA = 41,
+    /// HitachiSH
+    SH = 42,
+    /// SPARC Version9
+    SPARCV9 = 43,
+    /// Siemens TriCore embeddedprocessor
+    TRICORE = 44,
+    /// Argonaut RISC Core, Argonaut TechnologiesInc.
+    ARC = 45,
+    /// HitachiH8/300
+    H8_300 = 46,
+    /// HitachiH8/300H
+    H8_300H = 47,
+    /// HitachiH8S
+    H8S = 48,
+    /// HitachiH8/500
+    H8_500 = 49,
+    /// Intel IA-64 processorarchitecture
+    IA_64 = 50,
+    /// StanfordMIPS-X
+    MIPS_X = 51,
+    /// MotorolaColdFire
+    COLDFIRE = 52,
+    /// MotorolaM68HC12
+    M68HC12 = 53,
+    /// Fujitsu MMA MultimediaAccelerator
+    MMA = 54,
+    /// SiemensPCP
+    PCP = 55,
+    /// Sony nCPU embedded RISCprocessor
+    NCPU = 56,
+    /// Denso NDR1microprocessor
+    NDR1 = 57,
+    /// Motorola Star*Coreprocessor
+    STARCORE = 58,
+    /// Toyota ME16processor
+    ME16 = 59,
+    /// STMicroelectronics ST100processor
+    ST100 = 60,
+    /// Advanced Logic Corp. TinyJ embedded processorfamily
+    TINYJ = 61,
+    /// AMD x86-64architecture
+    X86_64 = 62,
+    /// Sony DSPProcessor
+    PDSP = 63,
+    /// Digital Equipment Corp.PDP-10
+    PDP10 = 64,
+    /// Digital Equipment Corp.PDP-11
+    PDP11 = 65,
+    /// Siemens FX66microcontroller
+    FX66 = 66,
+    /// STMicroelectronics ST9+ 8/16 bitmicrocontroller
+    ST9PLUS = 67,
+    /// STMicroelectronics ST7 8-bitmicrocontroller
+    ST7 = 68,
+    /// Motorola MC68HC16Microcontroller
+    M68HC16 = 69,
+    /// Motorola MC68HC11Microcontroller
+    M68HC11 = 70,
+    /// Motorola MC68HC08Microcontroller
+    M68HC08 = 71,
+    /// Motorola MC68HC05Microcontroller
+    M68HC05 = 72,
+    /// Silicon GraphicsSVx
+    SVX = 73,
+    /// STMicroelectronics ST19 8-bitmicrocontroller
+    ST19 = 74,
+    /// DigitalVAX
+    VAX = 75,
+    /// Axis Communications 32-bit embeddedprocessor
+    CRIS = 76,
+    /// Infineon Technologies 32-bit embeddedprocessor
+    JAVELIN = 77,
+    /// Element 14 64-bit DSPProcessor
+    FIREPATH = 78,
+    /// LSI Logic 16-bit DSPProcessor
+    ZSP = 79,
+    /// Donald Knuth's educational 64-bitprocessor
+    MMIX = 80,
+    /// Harvard University machine-independent objectfiles
+    HUANY = 81,
+    /// SiTeraPrism
+    PRISM = 82,
+    /// Atmel AVR 8-bitmicrocontroller
+    AVR = 83,
+    /// FujitsuFR30
+    FR30 = 84,
+    /// MitsubishiD10V
+    D10V = 85,
+    /// MitsubishiD30V
+    D30V = 86,
+    /// NECv850
+    V850 = 87,
+    /// MitsubishiM32R
+    M32R = 88,
+    /// MatsushitaMN10300
+    MN10300 = 89,
+    /// MatsushitaMN10200
+    MN10200 = 90,
+    /// picoJava
+    PJ = 91,
+    /// OpenRISC 32-bit embeddedprocessor
+    OPENRISC = 92,
+    /// ARC CoresTangent-A5
+    ARC_A5 = 93,
+    /// Tensilica XtensaArchitecture
+    XTENSA = 94,
+    /// Alphamosaic VideoCoreprocessor
+    VIDEOCORE = 95,
+    /// Thompson Multimedia General PurposeProcessor
+    TMM_GPP = 96,
+    /// National Semiconductor 32000series
+    NS32K = 97,
+    /// Tenor Network TPCprocessor
+    TPC = 98,
+    /// Trebia SNP 1000processor
+    SNP1K = 99,
+    /// STMicroelectronics (www.st.com) ST200microcontroller
+    ST200 = 100,
+    /// Ubicom IP2xxx microcontrollerfamily
+    IP2K = 101,
+    /// MAXProcessor
+    MAX = 102,
+    /// National Semiconductor CompactRISCmicroprocessor
+    CR = 103,
+    /// FujitsuF2MC16
+    F2MC16 = 104,
+    /// Texas Instruments embedded microcontrollermsp430
+    MSP430 = 105,
+    /// Analog Devices Blackfin (DSP)processor
+    BLACKFIN = 106,
+    /// S1C33 Family of Seiko Epsonprocessors
+    SE_C33 = 107,
+    /// Sharp embeddedmicroprocessor
+    SEP = 108,
+    /// Arca RISCMicroprocessor
+    ARCA = 109,
+    /// Microprocessor series from PKU-Unity Ltd. and MPRC of PekingUniversity
+    UNICORE = 110,
+    /// eXcess configurable cpu
+    EXCESS = 111,
+    /// Icera Semi. Deep Execution Processor
+    DXP = 112,
+    /// Altera Nios II
+    ALTERA_NIOS2 = 113,
+    /// National Semi. CompactRISC CRX
+    CRX = 114,
+    /// Motorola XGATE
+    XGATE = 115,
+    /// Infineon C16x/XC16x
+    C166 = 116,
+    /// Renesas M16C
+    M16C = 117,
+    /// Microchip Technology dsPIC30F
+    DSPIC30F = 118,
+    /// Freescale Communication Engine RISC
+    CE = 119,
+    /// Renesas M32C
+    M32C = 120,
+    /// Altium TSK3000
+    TSK3000 = 131,
+    /// Freescale RS08
+    RS08 = 132,
+    /// Analog Devices SHARC family
+    SHARC = 133,
+    /// Cyan Technology eCOG2
+    ECOG2 = 134,
+    /// Sunplus S+core7 RISC
+    SCORE7 = 135,
+    /// New Japan Radio (NJR) 24-bit DSP
+    DSP24 = 136,
+    /// Broadcom VideoCore III
+    VIDEOCORE3 = 137,
+    /// RISC for Lattice FPGA
+    LATTICEMICO32 = 138,
+    /// Seiko Epson C17
+    SE_C17 = 139,
+    /// Texas Instruments TMS320C6000 DSP
+    TI_C6000 = 140,
+    /// Texas Instruments TMS320C2000 DSP
+    TI_C2000 = 141,
+    /// Texas Instruments TMS320C55x DSP
+    TI_C5500 = 142,
+    /// Texas Instruments App. Specific RISC
+    TI_ARP32 = 143,
+    /// Texas Instruments Prog. Realtime Unit
+    TI_PRU = 144,
+    /// STMicroelectronics 64bit VLIW DSP
+    MMDSP_PLUS = 160,
+    /// Cypress M8C
+    CYPRESS_M8C = 161,
+    /// Renesas R32C
+    R32C = 162,
+    /// NXP Semi. TriMedia
+    TRIMEDIA = 163,
+    /// QUALCOMM DSP6
+    QDSP6 = 164,
+    /// Intel 8051 and variants
+    I8051 = 165,
+    /// STMicroelectronics STxP7x
+    STXP7X = 166,
+    /// Andes Tech. compact code emb. RISC
+    NDS32 = 167,
+    /// Cyan Technology eCOG1X
+    ECOG1X = 168,
+    /// Dallas Semi. MAXQ30 mc
+    MAXQ30 = 169,
+    /// New Japan Radio (NJR) 16-bit DSP
+    XIMO16 = 170,
+    /// M2000 Reconfigurable RISC
+    MANIK = 171,
+    /// Cray NV2 vector architecture
+    CRAYNV2 = 172,
+    /// Renesas RX
+    RX = 173,
+    /// Imagination Tech. META
+    METAG = 174,
+    /// MCST Elbrus
+    MCST_ELBRUS = 175,
+    /// Cyan Technology eCOG16
+    ECOG16 = 176,
+    /// National Semi. CompactRISC CR16
+    CR16 = 177,
+    /// Freescale Extended Time Processing Unit
+    ETPU = 178,
+    /// Infineon Tech. SLE9X
+    SLE9X = 179,
+    /// Intel L10M
+    L10M = 180,
+    /// Intel K10M
+    K10M = 181,
+    /// ARM AARCH64
+    AARCH64 = 183,
+    /// Amtel 32-bit microprocessor
+    AVR32 = 185,
+    /// STMicroelectronics STM8
+    STM8 = 186,
+    /// Tilera TILE64
+    TILE64 = 187,
+    /// Tilera TILEPro
+    TILEPRO = 188,
+    /// Xilinx MicroBlaze
+    MICROBLAZE = 189,
+    /// NVIDIA CUDA
+    CUDA = 190,
+    /// Tilera TILE-Gx
+    TILEGX = 191,
+    /// CloudShield
+    CLOUDSHIELD = 192,
+    /// KIPO-KAIST Core-A 1st gen
+    COREA_1ST = 193,
+    /// KIPO-KAIST Core-A 2nd gen
+    COREA_2ND = 194,
+    /// Synopsys ARCv2 ISA
+    ARCV2 = 195,
+    /// Open8 RISC
+    OPEN8 = 196,
+    /// Renesas RL78
+    RL78 = 197,
+    /// Broadcom VideoCore V
+    VIDEOCORE5 = 198,
+    /// Renesas 78KOR
+    R78KOR = 199,
+    /// Freescale 56800EX DSC
+    F56800EX = 200,
+    /// Beyond BA1
+    BA1 = 201,
+    /// Beyond BA2
+    BA2 = 202,
+    /// XMOS xCORE
+    XCORE = 203,
+    /// Microchip 8-bit PIC(r)
+    MCHP_PIC = 204,
+    /// Intel Graphics Technology
+    INTELGT = 205,
+    /// KM211 KM32
+    KM32 = 210,
+    /// KM211 KMX32
+    KMX32 = 211,
+    /// KM211 KMX16
+    EMX16 = 212,
+    /// KM211 KMX8
+    EMX8 = 213,
+    /// KM211 KVARC
+    KVARC = 214,
+    /// Paneve CDP
+    CDP = 215,
+    /// Cognitive Smart Memory Processor
+    COGE = 216,
+    /// Bluechip CoolEngine
+    COOL = 217,
+    /// Nanoradio Optimized RISC
+    NORC = 218,
+    /// CSR Kalimba
+    CSR_KALIMBA = 219,
+    /// Zilog Z80
+    Z80 = 220,
+    /// Controls and Data Services VISIUMcore
+    VISIUM = 221,
+    /// FTDI Chip FT32
+    FT32 = 222,
+    /// Moxie processor
+    MOXIE = 223,
+    /// AMD GPU
+    AMDGPU = 224,
+    /// RISC-V
+    RISCV = 243,
+    /// Linux BPF -- in-kernel virtual machine
+    BPF = 247,
+    /// C-SKY
+    CSKY = 252,
+    /// LoongArch
+    LOONGARCH = 258,
+}
+
+impl ElfMachine {
+    /// Natural pointer width in bytes of the machine, 4 or 8. `None` for the families with both
+    /// 32 and 64-bit variants under the same `e_machine` (MIPS, RISC-V, S390, ...) and the
+    /// machines we do not know about, see `ElfFormat::pointer_size` for those.
+    pub fn pointer_size(&self) -> Option<usize> {
+        match *self {
+            ElfMachine::X86_64 | ElfMachine::AARCH64 | ElfMachine::PPC64 | ElfMachine::IA_64 |
+            ElfMachine::ALPHA | ElfMachine::SPARCV9 | ElfMachine::TILEGX |
+            ElfMachine::AMDGPU | ElfMachine::BPF | ElfMachine::LOONGARCH => Some(8),
+            ElfMachine::I386 | ElfMachine::ARM | ElfMachine::PPC | ElfMachine::SPARC |
+            ElfMachine::SPARC32PLUS | ElfMachine::M68K | ElfMachine::SH | ElfMachine::PARISC |
+            ElfMachine::XTENSA | ElfMachine::MICROBLAZE | ElfMachine::CSKY |
+            ElfMachine::ALTERA_NIOS2 | ElfMachine::OPENRISC | ElfMachine::ARCV2 |
+            ElfMachine::TILEPRO => Some(4),
+            _ => None,
+        }
+    }
+}
+
+/// Information provided by Elf header is provided by functions of this trait.
+pub trait ElfHeader {
+    /// Elf File type
+    fn elf_type(&self) -> Result<ElfType, RustepError>;
+    /// Elf machine
+    fn machine(&self) -> Result<ElfMachine, RustepError>;
+    /// Entry point
+    fn entry(&self) -> u64;
+    /// Program header offset
+    fn phoff(&self) -> u64;
+    /// Section header offset
+    fn shoff(&self) -> u64;
+    fn ehsize(&self) -> u64;
+    /// program header entry size
+    fn phentsize(&self) -> u64;
+    /// program header number
+    fn phnum(&self) -> u64;
+    /// section header entry size
+    fn shentsize(&self) -> u64;
+    /// section header number
+    fn shnum(&self) -> u64;
+    /// section header of string table index
+    fn shstrndx(&self) -> u64;
+    /// `e_version`, always `EV_CURRENT` (1) for valid files
+    fn version(&self) -> u32;
+    /// Raw `e_flags`, the meaning is architecture defined. See `decode_flags`.
+    fn flags(&self) -> u32;
+    /// Raw `e_ident`, magic, class, data encoding, version and OS ABI
+    fn ident(&self) -> &[u8; 16];
+    /// Encodes the header back into its on-disk little endian layout.
+    fn to_bytes(&self) -> Vec<u8>;
+    /// The raw header, an `Elf32_Ehdr` or an `Elf64_Ehdr` depending on the class, for
+    /// downcasting when the fields are needed as they are stored.
+    fn as_any(&self) -> &Any;
+}
+
+/// Known `e_flags` values as `(mask, value, name)`. A flag applies when `flags & mask == value`.
+const ARM_FLAGS: &'static [(u32, u32, &'static str)] = &[
+    (0xff000000, 0x01000000, "EF_ARM_EABI_VER1"),
+    (0xff000000, 0x02000000, "EF_ARM_EABI_VER2"),
+    (0xff000000, 0x03000000, "EF_ARM_EABI_VER3"),
+    (0xff000000, 0x04000000, "EF_ARM_EABI_VER4"),
+    (0xff000000, 0x05000000, "EF_ARM_EABI_VER5"),
+    (0x00800000, 0x00800000, "EF_ARM_BE8"),
+    (0x00400000, 0x00400000, "EF_ARM_LE8"),
+    (0x00000200, 0x00000200, "EF_ARM_ABI_FLOAT_SOFT"),
+    (0x00000400, 0x00000400, "EF_ARM_ABI_FLOAT_HARD"),
+];
+
+const MIPS_FLAGS: &'static [(u32, u32, &'static str)] = &[
+    (0x00000001, 0x00000001, "EF_MIPS_NOREORDER"),
+    (0x00000002, 0x00000002, "EF_MIPS_PIC"),
+    (0x00000004, 0x00000004, "EF_MIPS_CPIC"),
+    (0x00000008, 0x00000008, "EF_MIPS_XGOT"),
+    (0x00000010, 0x00000010, "EF_MIPS_64BIT_WHIRL"),
+    (0x00000020, 0x00000020, "EF_MIPS_ABI2"),
+    (0x00000040, 0x00000040, "EF_MIPS_ABI_ON32"),
+    (0x00000200, 0x00000200, "EF_MIPS_FP64"),
+    (0x00000400, 0x00000400, "EF_MIPS_NAN2008"),
+    (0x0000f000, 0x00001000, "EF_MIPS_ABI_O32"),
+    (0x0000f000, 0x00002000, "EF_MIPS_ABI_O64"),
+    (0x0000f000, 0x00003000, "EF_MIPS_ABI_EABI32"),
+    (0x0000f000, 0x00004000, "EF_MIPS_ABI_EABI64"),
+    (0xf0000000, 0x00000000, "EF_MIPS_ARCH_1"),
+    (0xf0000000, 0x10000000, "EF_MIPS_ARCH_2"),
+    (0xf0000000, 0x20000000, "EF_MIPS_ARCH_3"),
+    (0xf0000000, 0x30000000, "EF_MIPS_ARCH_4"),
+    (0xf0000000, 0x40000000, "EF_MIPS_ARCH_5"),
+    (0xf0000000, 0x50000000, "EF_MIPS_ARCH_32"),
+    (0xf0000000, 0x60000000, "EF_MIPS_ARCH_64"),
+    (0xf0000000, 0x70000000, "EF_MIPS_ARCH_32R2"),
+    (0xf0000000, 0x80000000, "EF_MIPS_ARCH_64R2"),
+    (0xf0000000, 0x90000000, "EF_MIPS_ARCH_32R6"),
+    (0xf0000000, 0xa0000000, "EF_MIPS_ARCH_64R6"),
+];
+
+const RISCV_FLAGS: &'static [(u32, u32, &'static str)] = &[
+    (0x0001, 0x0001, "EF_RISCV_RVC"),
+    (0x0006, 0x0000, "EF_RISCV_FLOAT_ABI_SOFT"),
+    (0x0006, 0x0002, "EF_RISCV_FLOAT_ABI_SINGLE"),
+    (0x0006, 0x0004, "EF_RISCV_FLOAT_ABI_DOUBLE"),
+    (0x0006, 0x0006, "EF_RISCV_FLOAT_ABI_QUAD"),
+    (0x0008, 0x0008, "EF_RISCV_RVE"),
+    (0x0010, 0x0010, "EF_RISCV_TSO"),
+];
+
+/// Renders the architecture defined `e_flags` into their symbolic names. ARM, MIPS and RISC-V
+/// are understood, bits that cannot be interpreted are reported as a single hex value.
+///
+/// # Examples
+/// ```
+/// use rustep::format::elf::{decode_flags, ElfMachine};
+///
+/// assert_eq!(decode_flags(ElfMachine::ARM, 0x05000400),
+///            vec!["EF_ARM_EABI_VER5", "EF_ARM_ABI_FLOAT_HARD"]);
+/// ```
+pub fn decode_flags(machine: ElfMachine, flags: u32) -> Vec<String> {
+    let known = match machine {
+        ElfMachine::ARM => ARM_FLAGS,
+        ElfMachine::MIPS | ElfMachine::MIPS_RS3_LE => MIPS_FLAGS,
+        ElfMachine::RISCV => RISCV_FLAGS,
+        _ => &[],
+    };
+    let mut decoded = Vec::new();
+    let mut handled = 0;
+    for &(mask, value, name) in known.iter() {
+        if flags & mask == value {
+            decoded.push(name.to_string());
+            handled |= mask;
+        }
+    }
+
+    let rest = flags & !handled;
+    if rest != 0 {
+        decoded.push(format!("{:#x}", rest));
+    }
+
+    decoded
+}
+
+impl ElfHeader for Elf32_Ehdr {
+    fn elf_type(&self) -> Result<ElfType, RustepError> {
+        Ok(FromPrimitive::from_u16(self.e_type)
+            .ok_or(RustepError::ElfType(self.e_type as u64))?)
+    }
+
+    fn machine(&self) -> Result<ElfMachine, RustepError> {
+        Ok(FromPrimitive::from_u16(self.e_machine)
+            .ok_or(RustepError::ElfMachine(self.e_machine as u64))?)
+    }
+
+    fn entry(&self) -> u64 {
+        self.e_entry as u64
+    }
+
+    fn phoff(&self) -> u64 {
+        self.e_phoff as u64
+    }
+
+    fn shoff(&self) -> u64 {
+        self.e_shoff as u64
+    }
+
+    fn ehsize(&self) -> u64 {
+        self.e_ehsize as u64
+    }
+
+    fn phentsize(&self) -> u64 {
+        self.e_phentsize as u64
+    }
+
+    fn phnum(&self) -> u64 {
+        self.e_phnum as u64
+    }
+
+    fn shentsize(&self) -> u64 {
+        self.e_shentsize as u64
+    }
+
+    fn shnum(&self) -> u64 {
+        self.e_shnum as u64
+    }
+
+    fn shstrndx(&self) -> u64 {
+        self.e_shstrndx as u64
+    }
+
+    fn version(&self) -> u32 {
+        self.e_version
+    }
+
+    fn flags(&self) -> u32 {
+        self.e_flags
+    }
+
+    fn ident(&self) -> &[u8; 16] {
+        &self.e_ident
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(mem::size_of::<Elf32_Ehdr>());
+        bytes.extend_from_slice(&self.e_ident);
+        bytes.extend_from_slice(&self.e_type.to_le_bytes());
+        bytes.extend_from_slice(&self.e_machine.to_le_bytes());
+        bytes.extend_from_slice(&self.e_version.to_le_bytes());
+        bytes.extend_from_slice(&self.e_entry.to_le_bytes());
+        bytes.extend_from_slice(&self.e_phoff.to_le_bytes());
+        bytes.extend_from_slice(&self.e_shoff.to_le_bytes());
+        bytes.extend_from_slice(&self.e_flags.to_le_bytes());
+        bytes.extend_from_slice(&self.e_ehsize.to_le_bytes());
+        bytes.extend_from_slice(&self.e_phentsize.to_le_bytes());
+        bytes.extend_from_slice(&self.e_phnum.to_le_bytes());
+        bytes.extend_from_slice(&self.e_shentsize.to_le_bytes());
+        bytes.extend_from_slice(&self.e_shnum.to_le_bytes());
+        bytes.extend_from_slice(&self.e_shstrndx.to_le_bytes());
+
+        bytes
+    }
+
+    fn as_any(&self) -> &Any {
+        self
+    }
+}
+
+impl ElfHeader for Elf64_Ehdr {
+    fn elf_type(&self) -> Result<ElfType, RustepError> {
+        Ok(FromPrimitive::from_u16(self.e_type)
+            .ok_or(RustepError::ElfType(self.e_type as u64))?)
+    }
+
+    fn machine(&self) -> Result<ElfMachine, RustepError> {
+        Ok(FromPrimitive::from_u16(self.e_machine)
+            .ok_or(RustepError::ElfMachine(self.e_machine as u64))?)
+    }
+
+    fn entry(&self) -> u64 {
+        self.e_entry as u64
+    }
+
+    fn phoff(&self) -> u64 {
+        self.e_phoff as u64
+    }
+
+   fn shoff(&self) -> u64 {
+       self.e_shoff as u64
+    }
+
+   fn ehsize(&self) -> u64 {
+       self.e_ehsize as u64
+   }
+
+   fn phentsize(&self) -> u64 {
+       self.e_phentsize as u64
+   }
+
+   fn phnum(&self) -> u64 {
+       self.e_phnum as u64
+   }
+
+   fn shentsize(&self) -> u64 {
+       self.e_shentsize as u64
+   }
+
+   fn shnum(&self) -> u64 {
+       self.e_shnum as u64
+   }
+
+   fn shstrndx(&self) -> u64 {
+       self.e_shstrndx as u64
+   }
+
+   fn version(&self) -> u32 {
+       self.e_version
+   }
+
+   fn flags(&self) -> u32 {
+       self.e_flags
+   }
+
+   fn ident(&self) -> &[u8; 16] {
+       &self.e_ident
+   }
+
+   fn to_bytes(&self) -> Vec<u8> {
+       let mut bytes = Vec::with_capacity(mem::size_of::<Elf64_Ehdr>());
+       bytes.extend_from_slice(&self.e_ident);
+       bytes.extend_from_slice(&self.e_type.to_le_bytes());
+       bytes.extend_from_slice(&self.e_machine.to_le_bytes());
+       bytes.extend_from_slice(&self.e_version.to_le_bytes());
+       bytes.extend_from_slice(&self.e_entry.to_le_bytes());
+       bytes.extend_from_slice(&self.e_phoff.to_le_bytes());
+       bytes.extend_from_slice(&self.e_shoff.to_le_bytes());
+       bytes.extend_from_slice(&self.e_flags.to_le_bytes());
+       bytes.extend_from_slice(&self.e_ehsize.to_le_bytes());
+       bytes.extend_from_slice(&self.e_phentsize.to_le_bytes());
+       bytes.extend_from_slice(&self.e_phnum.to_le_bytes());
+       bytes.extend_from_slice(&self.e_shentsize.to_le_bytes());
+       bytes.extend_from_slice(&self.e_shnum.to_le_bytes());
+       bytes.extend_from_slice(&self.e_shstrndx.to_le_bytes());
+
+       bytes
+   }
+
+    fn as_any(&self) -> &Any {
+        self
+    }
+}
+
+impl<'a> fmt::Display for ElfHeader + 'a {
+    /// A block similar to `readelf -h`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "ELF Header:")?;
+        match self.elf_type() {
+            Ok(t) => writeln!(f, "  Type:                              {:?}", t)?,
+            Err(e) => writeln!(f, "  Type:                              {}", e)?,
+        }
+        match self.machine() {
+            Ok(m) => writeln!(f, "  Machine:                           {:?}", m)?,
+            Err(e) => writeln!(f, "  Machine:                           {}", e)?,
+        }
+        writeln!(f, "  Entry point address:               {:#x}", self.entry())?;
+        writeln!(f, "  Start of program headers:          {} (bytes into file)", self.phoff())?;
+        writeln!(f, "  Start of section headers:          {} (bytes into file)", self.shoff())?;
+        writeln!(f, "  Size of this header:               {} (bytes)", self.ehsize())?;
+        writeln!(f, "  Size of program headers:           {} (bytes)", self.phentsize())?;
+        writeln!(f, "  Number of program headers:         {}", self.phnum())?;
+        writeln!(f, "  Size of section headers:           {} (bytes)", self.shentsize())?;
+        writeln!(f, "  Number of section headers:         {}", self.shnum())?;
+        write!(f, "  Section header string table index: {}", self.shstrndx())
+    }
+}
+
+/// A trait representing the supported methods for a parsed ELF format.
+/// This is used as universal interface for Elf file format, some methods are useful when using
+/// those ignoring the 32 or 64 part. The information provided by `ELF` header can be extracted 
+/// from the `ElfHeader` trait object which can be gained from `header()` method. 
+///
+pub trait ElfFormat {
+    /// Get trait object of header
+    fn header(&self) -> &ElfHeader;
+    /// all segments trait objects
+    fn segments(&self) -> Vec<&ElfSegment>;
+    /// all sections trait objects
+    fn sections(&self) -> Vec<&ElfSection>;
+    /// A columnar listing of all section headers like `readelf -S`. Addresses are padded to 8
+    /// digits for 32-bit files and 16 digits for 64-bit files.
+    fn format_section_table(&self) -> String;
+    /// Symbols of the `.symtab` section, named through its linked string table (`.strtab`).
+    /// Empty if the binary is stripped, see `dynamic_symbols` then.
+    fn symbols(&self) -> Vec<&ElfSymbol>;
+    /// Symbols of the `.dynsym` section, the ones used by the dynamic linker, named through its
+    /// linked string table (`.dynstr`). Stripping keeps them.
+    fn dynamic_symbols(&self) -> Vec<&ElfSymbol>;
+    /// Symbols this file expects another module to provide: the undefined (`SHN_UNDEF`) global
+    /// and weak entries of `.dynsym`, like `nm -D --undefined-only`.
+    fn imports(&self) -> Vec<&ElfSymbol> {
+        self.dynamic_symbols()
+            .into_iter()
+            .filter(|sym| sym.section_index() == SHN_UNDEF && is_linkable_symbol(*sym))
+            .collect()
+    }
+    /// Symbols this file provides to other modules: the defined global, weak and unique entries
+    /// of `.dynsym`, like `nm -D --defined-only`.
+    fn exports(&self) -> Vec<&ElfSymbol> {
+        self.dynamic_symbols()
+            .into_iter()
+            .filter(|sym| sym.section_index() != SHN_UNDEF && is_linkable_symbol(*sym))
+            .collect()
+    }
+    /// Parses the entries of a `SHT_REL` or `SHT_RELA` section, other sections have no entries.
+    fn relocations(&self, section: &ElfSection) -> Result<Vec<ElfRelocation>, RustepError>;
+    /// Size in bytes of the buffer the file was parsed from
+    fn file_size(&self) -> u64;
+    /// Number of section headers in the file, `e_shnum` or the extended count. More than
+    /// `sections().len()` when `ParseOptions::section_filter` left sections out.
+    fn section_count(&self) -> u64;
+    /// The program header table as found in the file, `e_phnum` entries of `e_phentsize` bytes
+    /// from `e_phoff`, cut at the end of the buffer. Empty when there is no table.
+    fn raw_program_headers(&self) -> &[u8];
+    /// The section header table as found in the file, `e_shnum` entries of `e_shentsize` bytes
+    /// from `e_shoff`, cut at the end of the buffer. Empty when there is no table.
+    fn raw_section_headers(&self) -> &[u8];
+    /// Data appended to the file, i.e. the bytes after the end of the last structure: the ELF
+    /// header, the header tables, the sections and the segments. `None` when the file ends with
+    /// one of them.
+    fn overlay(&self) -> Option<&[u8]>;
+    /// Whether this is an `ELFCLASS64` file
+    fn is_64bit(&self) -> bool;
+    /// Size in bytes of the pointers stored in the file, e.g. in `.init_array` or the GOT. The
+    /// class decides, as the ABIs with 32-bit pointers on 64-bit machines (x32, AArch64 ILP32)
+    /// use `ELFCLASS32`. It matches `ElfMachine::pointer_size` for the usual ABIs.
+    fn pointer_size(&self) -> usize {
+        if self.is_64bit() {
+            8
+        } else {
+            4
+        }
+    }
+    /// Architecture of `e_machine`, `Arch::Unknown` for machines outside the usual families
+    /// or unknown values.
+    fn arch(&self) -> Arch {
+        self.header().machine().map(Arch::from).unwrap_or(Arch::Unknown)
+    }
+    /// Byte order declared by `e_ident[EI_DATA]`
+    fn endianness(&self) -> Endianness {
+        match self.header().ident()[EI_DATA as usize] as u32 {
+            ELFDATA2MSB => Endianness::Big,
+            // The parser reads everything as little endian, invalid values included
+            _ => Endianness::Little,
+        }
+    }
+    /// Entries of the `PT_DYNAMIC` segment up to `DT_NULL`, empty for statically linked files.
+    fn dynamic(&self) -> Result<Vec<DynamicEntry>, RustepError>;
+    /// get some specific section with a given name
+    fn section(&self, name: &str) -> Option<&ElfSection> {
+        for sec in self.sections().iter() {
+            if sec.name() == name {
+                return Some(*sec)
+            }
+        }
+
+        None
+    }
+    /// Same as `section`, with `RustepError::SectionNotFound` when there is no such section, for
+    /// use with `?`.
+    fn section_or_err(&self, name: &str) -> Result<&ElfSection, RustepError> {
+        self.section(name).ok_or_else(|| RustepError::SectionNotFound(name.to_string()))
+    }
+    /// Index of the section with the given name in the section header table.
+    fn section_index(&self, name: &str) -> Option<usize> {
+        self.section(name).map(|sec| sec.index())
+    }
+    /// The section at `index` in the section header table, the index `sh_link`, `sh_info` and
+    /// `st_shndx` refer to. Same as indexing `sections()`, unless
+    /// `ParseOptions::section_filter` left sections out.
+    fn section_by_index(&self, index: usize) -> Option<&ElfSection> {
+        let sections = self.sections();
+        // Sections are kept in header order, so without filtering the position is the index
+        match sections.get(index) {
+            Some(sec) if sec.index() == index => Some(*sec),
+            _ => sections
+                .binary_search_by_key(&index, |sec| sec.index())
+                .ok()
+                .map(|position| sections[position]),
+        }
+    }
+    /// The section holding the section names (`.shstrtab`), at `e_shstrndx`. An index too
+    /// large for the header (`SHN_XINDEX`) is read from `sh_link` of the first section header.
+    fn section_header_strtab(&self) -> Option<&ElfSection> {
+        let index = match self.header().shstrndx() {
+            index if index == SHN_XINDEX as u64 => self.section_by_index(0)?.shdr().link(),
+            index => index,
+        };
+
+        self.section_by_index(index as usize)
+    }
+    /// The allocated (`SHF_ALLOC`) section whose address range contains `address`. Empty
+    /// sections contain nothing. Always `None` for relocatable objects, whose sections are not
+    /// given addresses yet, see `symbol_location` and `relocation_location` instead.
+    fn section_at_address(&self, address: u64) -> Option<&ElfSection> {
+        if self.is_relocatable() {
+            return None;
+        }
+        self.sections().into_iter().find(|sec| {
+            let shdr = sec.shdr();
+            sec.is_alloc() && address >= shdr.address() && address - shdr.address() < shdr.size()
+        })
+    }
+    /// The allocated (`SHF_ALLOC`) sections sorted by address, the order of a memory map.
+    /// Sections at the same address, like an empty one and the next, keep their header order.
+    fn sections_by_address(&self) -> Vec<&ElfSection> {
+        let mut sections: Vec<&ElfSection> = self.sections()
+            .into_iter()
+            .filter(|sec| sec.is_alloc())
+            .collect();
+        sections.sort_by_key(|sec| sec.shdr().address());
+
+        sections
+    }
+    /// The `PT_LOAD` segments sorted by virtual address. The specification already requires
+    /// this order in the program header table.
+    fn segments_by_address(&self) -> Vec<&ElfSegment> {
+        let mut segments: Vec<&ElfSegment> = self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .collect();
+        segments.sort_by_key(|seg| seg.phdr().vaddr());
+
+        segments
+    }
+    /// An index of the sections and symbols by address, for resolving many addresses in
+    /// logarithmic time instead of scanning like `section_at_address` does.
+    fn address_index(&self) -> AddressIndex {
+        AddressIndex::new(self)
+    }
+    /// Whether this is a relocatable object (`ET_REL`). Symbol values and relocation offsets
+    /// are then relative to a section rather than virtual addresses.
+    fn is_relocatable(&self) -> bool {
+        self.header().elf_type().ok() == Some(ElfType::ET_REL)
+    }
+    /// The section `symbol` is defined in, with the offset of the symbol into it. Symbol values
+    /// are taken as offsets in relocatable objects and as addresses otherwise. `None` for
+    /// undefined, absolute and common symbols.
+    fn symbol_location(&self, symbol: &ElfSymbol) -> Option<(&ElfSection, u64)> {
+        let index = symbol.section_index();
+        // SHN_LORESERVE and above are not section indices
+        if index == SHN_UNDEF || index >= 0xff00 {
+            return None;
+        }
+        let sec = self.section_by_index(index as usize)?;
+        if self.is_relocatable() {
+            Some((sec, symbol.value()))
+        } else {
+            symbol.value().checked_sub(sec.shdr().address()).map(|offset| (sec, offset))
+        }
+    }
+    /// The file contents of the symbol called `name`, its `st_size` bytes from `st_value`, e.g.
+    /// the code of a function. `.symtab` is searched first, then `.dynsym`. `None` for undefined
+    /// symbols, symbols of no size and symbols in sections taking no room in the file like
+    /// `.bss`.
+    fn symbol_bytes(&self, name: &str) -> Option<&[u8]> {
+        let symbol = self.symbols()
+            .into_iter()
+            .chain(self.dynamic_symbols())
+            .find(|sym| sym.name() == name && sym.section_index() != SHN_UNDEF)?;
+        if symbol.size() == 0 {
+            return None;
+        }
+        let (sec, offset) = self.symbol_location(symbol)?;
+        // The header gives `.bss` a file range anyway, holding whatever follows
+        if *sec.section_type() == SectionType::SHT_NOBITS {
+            return None;
+        }
+
+        sec.data().get(offset as usize..)?.get(..symbol.size() as usize)
+    }
+    /// The section the entries of relocation section `section` apply to, given by its
+    /// `sh_info`. `None` for dynamic relocations, which are not tied to a section.
+    fn relocated_section(&self, section: &ElfSection) -> Option<&ElfSection> {
+        match section.shdr().info() {
+            0 => None,
+            index => self.section_by_index(index as usize),
+        }
+    }
+    /// The section `reloc`, an entry of relocation section `section`, patches, with the offset
+    /// of the patched bytes into it. `r_offset` is an offset into the relocated section in
+    /// relocatable objects and a virtual address otherwise.
+    fn relocation_location(
+        &self,
+        section: &ElfSection,
+        reloc: &ElfRelocation
+    ) -> Option<(&ElfSection, u64)> {
+        if self.is_relocatable() {
+            return self.relocated_section(section).map(|sec| (sec, reloc.offset()));
+        }
+        let sec = self.section_at_address(reloc.offset())?;
+
+        Some((sec, reloc.offset() - sec.shdr().address()))
+    }
+    /// Sections whose contents lie within the segment, like the "Section to Segment mapping" of
+    /// `readelf -l`. The file range (`sh_offset`/`sh_size` against `p_offset`/`p_filesz`) must
+    /// fit, and for `SHF_ALLOC` sections the address range as well.
+    fn sections_in_segment(&self, segment: &ElfSegment) -> Vec<&ElfSection> {
+        self.sections()
+            .into_iter()
+            // The null section, `section_filter` may have dropped it
+            .filter(|sec| sec.index() != 0)
+            .filter(|sec| section_in_segment(*sec, segment))
+            .collect()
+    }
+    /// The readelf style "Section to Segment mapping" grid, one line per segment listing the
+    /// names from `sections_in_segment`.
+    fn format_segment_mapping(&self) -> String {
+        let mut mapping = String::from(" Section to Segment mapping:\n  Segment Sections...\n");
+        for (i, seg) in self.segments().iter().enumerate() {
+            let names: Vec<&str> = self.sections_in_segment(*seg)
+                .iter()
+                .map(|sec| sec.name())
+                .collect();
+            mapping.push_str(&format!("   {:02}     {}\n", i, names.join(" ")));
+        }
+
+        mapping
+    }
+    /// Names of the symbols the file exports to the dynamic linker, the defined global, weak and
+    /// `STB_GNU_UNIQUE` ones of `.dynsym`, sorted and without duplicates. This is the list a
+    /// linker version script controls, and what `nm -D --defined-only` shows, so comparing it
+    /// between two versions of a library gives the symbols added or removed.
+    fn exported_symbol_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.dynamic_symbols()
+            .into_iter()
+            .filter(|sym| {
+                let exported = match sym.binding() {
+                    MaybeKnown::Known(SymbolBinding::STB_GLOBAL)
+                    | MaybeKnown::Known(SymbolBinding::STB_WEAK)
+                    | MaybeKnown::Known(SymbolBinding::STB_GNU_UNIQUE) => true,
+                    _ => false,
+                };
+                let symbol_type = sym.sym_type();
+                exported
+                    && !sym.name().is_empty()
+                    && sym.section_index() != SHN_UNDEF
+                    && symbol_type != SymbolType::STT_SECTION
+                    && symbol_type != SymbolType::STT_FILE
+            })
+            .map(|sym| sym.name().to_string())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        names
+    }
+    /// The `.symtab` symbols in the format of `nm`, one per line sorted by name: the value (empty
+    /// for undefined symbols), the one letter type code and the name. Section and file symbols
+    /// are left out, like `nm` does.
+    fn format_symbols(&self) -> String {
+        let width = if self.is_64bit() { 16 } else { 8 };
+        let mut symbols: Vec<&ElfSymbol> = self.symbols()
+            .into_iter()
+            .filter(|sym| {
+                let symbol_type = sym.sym_type();
+                !sym.name().is_empty()
+                    && symbol_type != SymbolType::STT_SECTION
+                    && symbol_type != SymbolType::STT_FILE
+            })
+            .collect();
+        symbols.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut lines = String::new();
+        for sym in symbols {
+            let code = nm_type_code(sym, self);
+            if sym.section_index() == SHN_UNDEF {
+                lines.push_str(&format!("{:w$} {} {}\n", "", code, sym.name(), w = width));
+            } else {
+                lines.push_str(&format!("{:0w$x} {} {}\n", sym.value(), code, sym.name(), w = width));
+            }
+        }
+
+        lines
+    }
+    /// Up to `len` bytes of code at the entry point, for a
+    /// [`Disassembler`](../../disasm/trait.Disassembler.html). `e_entry` is a virtual address,
+    /// it is turned into a file offset through the `PT_LOAD` segment containing it:
+    /// `p_offset + (e_entry - p_vaddr)`. The result is cut at the end of the segment's file
+    /// contents, and is `None` if no loaded segment maps the entry point.
+    fn entry_bytes(&self, len: usize) -> Option<&[u8]> {
+        let entry = self.header().entry();
+        self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .find(|seg| {
+                let phdr = seg.phdr();
+                entry >= phdr.vaddr() && entry - phdr.vaddr() < phdr.file_size()
+            })
+            .and_then(|seg| {
+                let start = (entry - seg.phdr().vaddr()) as usize;
+                // The contents are cut at the end of a truncated file
+                let data = seg.data().get(start..)?;
+                Some(&data[..data.len().min(len)])
+            })
+    }
+    /// The `len` bytes at virtual address `vaddr` as the loader maps them. The range must fit in
+    /// a single `PT_LOAD` segment, or when no segment covers it (e.g. stripped program headers)
+    /// in a single `SHF_ALLOC` section. The part beyond the file contents, like the `.bss` tail
+    /// of a segment or a `SHT_NOBITS` section, reads as zeros. `None` if the range is not mapped.
+    fn read_virtual(&self, vaddr: u64, len: usize) -> Option<Vec<u8>> {
+        let end = vaddr.checked_add(len as u64)?;
+        let segment = self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .map(|seg| (seg.phdr().vaddr(), seg.phdr().mem_size(), seg.data()))
+            .find(|&(start, size, _)| vaddr >= start && end - start <= size);
+        let (start, size, data) = match segment {
+            Some(segment) => segment,
+            None => {
+                let sec = self.section_at_address(vaddr)?;
+                let data = if *sec.section_type() == SectionType::SHT_NOBITS {
+                    &[]
+                } else {
+                    sec.data()
+                };
+                (sec.shdr().address(), sec.shdr().size(), data)
+            }
+        };
+        if end - start > size {
+            return None;
+        }
+        let offset = (vaddr - start) as usize;
+        let mut bytes = vec![0; len];
+        if offset < data.len() {
+            let copied = cmp::min(len, data.len() - offset);
+            bytes[..copied].copy_from_slice(&data[offset..offset + copied]);
+        }
+
+        Some(bytes)
+    }
+    /// The address range `[start, end)` spanned by the `PT_LOAD` segments, from the lowest
+    /// `p_vaddr` to the highest `p_vaddr + p_memsz`, i.e. what a loader has to reserve. `None`
+    /// if nothing is loaded.
+    fn load_bounds(&self) -> Option<(u64, u64)> {
+        self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .map(|seg| {
+                let phdr = seg.phdr();
+                // Hostile headers may overflow, the end is capped instead
+                (phdr.vaddr(), phdr.vaddr().saturating_add(phdr.mem_size()))
+            })
+            .fold(None, |bounds, (start, end)| match bounds {
+                Some((low, high)) => Some((cmp::min(low, start), cmp::max(high, end))),
+                None => Some((start, end)),
+            })
+    }
+    /// The lowest loaded virtual address, usually `0` for position independent files. `0` as
+    /// well when there is no `PT_LOAD` segment.
+    fn image_base(&self) -> u64 {
+        self.load_bounds().map_or(0, |(start, _)| start)
+    }
+    /// The `PT_LOAD` segments sorted by virtual address, as `/proc/<pid>/maps` would show the
+    /// file once loaded, before page rounding and relocation. A `size` above `file_size` is the
+    /// zero filled tail of the segment, `.bss` usually.
+    fn memory_map(&self) -> Vec<MemRegion> {
+        let mut regions: Vec<MemRegion> = self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .map(|seg| {
+                let phdr = seg.phdr();
+                let mut perms = String::with_capacity(4);
+                perms.push(if seg.is_readable() { 'r' } else { '-' });
+                perms.push(if seg.is_writable() { 'w' } else { '-' });
+                perms.push(if seg.is_executable() { 'x' } else { '-' });
+                // File mappings of the loader are private, copy on write
+                perms.push('p');
+                MemRegion {
+                    vaddr: phdr.vaddr(),
+                    size: phdr.mem_size(),
+                    file_offset: phdr.offset(),
+                    file_size: phdr.file_size(),
+                    perms: perms,
+                }
+            })
+            .collect();
+        regions.sort_by_key(|region| region.vaddr);
+
+        regions
+    }
+    /// Base virtual address and file contents of every executable (`PF_X`) `PT_LOAD` segment, for
+    /// scanning the code as it is mapped, e.g. when looking for gadgets. The contents stop at
+    /// `p_filesz`, the zero filled rest of the segment is not included.
+    fn executable_segments(&self) -> Vec<(u64, &[u8])> {
+        self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD && seg.is_executable())
+            .map(|seg| (seg.phdr().vaddr(), seg.data()))
+            .collect()
+    }
+    /// Same as `executable_segments` for the `SHF_EXECINSTR` sections, which also covers
+    /// relocatable objects. The addresses are the section addresses, `0` in relocatable objects.
+    fn executable_sections(&self) -> Vec<(u64, &[u8])> {
+        self.sections()
+            .into_iter()
+            .filter(|sec| sec.is_executable())
+            .map(|sec| (sec.shdr().address(), sec.data()))
+            .collect()
+    }
+    /// Total size of the code, the `sh_size` of every `SHF_EXECINSTR` section.
+    fn code_size(&self) -> u64 {
+        self.sections()
+            .into_iter()
+            .filter(|sec| sec.is_executable())
+            .map(|sec| sec.shdr().size())
+            .sum()
+    }
+    /// Total size of the writable data in memory, the `sh_size` of every allocated writable
+    /// section which is not executable. Like the `data` and `bss` columns of `size`, `.bss` and
+    /// the other `SHT_NOBITS` sections count, see `data_file_size` for the bytes in the file.
+    fn data_size(&self) -> u64 {
+        self.sections()
+            .into_iter()
+            .filter(|sec| sec.is_alloc() && sec.is_writable() && !sec.is_executable())
+            .map(|sec| sec.shdr().size())
+            .sum()
+    }
+    /// Same as `data_size` without the `SHT_NOBITS` sections, which take no room in the file.
+    fn data_file_size(&self) -> u64 {
+        self.sections()
+            .into_iter()
+            .filter(|sec| sec.is_alloc() && sec.is_writable() && !sec.is_executable())
+            .filter(|sec| *sec.section_type() != SectionType::SHT_NOBITS)
+            .map(|sec| sec.shdr().size())
+            .sum()
+    }
+    /// The section containing the entry point, usually `.text`. `None` if there is no entry
+    /// point (`e_entry` is 0, e.g. in relocatable objects and most shared libraries).
+    fn entry_section(&self) -> Option<&ElfSection> {
+        match self.header().entry() {
+            0 => None,
+            entry => self.section_at_address(entry),
+        }
+    }
+    /// The symbol at the entry point, usually `_start`, or covering it when none starts there.
+    /// `.symtab` is looked up first, then `.dynsym`. Section and file symbols are ignored.
+    /// `None` if there is no entry point.
+    fn entry_symbol(&self) -> Option<&ElfSymbol> {
+        let entry = match self.header().entry() {
+            0 => return None,
+            entry => entry,
+        };
+        let candidates: Vec<&ElfSymbol> = self.symbols()
+            .into_iter()
+            .chain(self.dynamic_symbols())
+            .filter(|sym| sym.section_index() != SHN_UNDEF)
+            .filter(|sym| {
+                let symbol_type = sym.sym_type();
+                symbol_type != SymbolType::STT_SECTION && symbol_type != SymbolType::STT_FILE
+            })
+            .collect();
+
+        candidates
+            .iter()
+            .find(|sym| sym.value() == entry)
+            .or_else(|| candidates
+                .iter()
+                .find(|sym| entry >= sym.value() && entry - sym.value() < sym.size()))
+            .map(|sym| *sym)
+    }
+    /// Virtual addresses of every occurrence of `needle` in the file contents of the `PT_LOAD`
+    /// segments, sorted ascending. Each segment is searched on its own, so a match spanning two
+    /// adjacent segments is not found, and neither is one in the zero filled part beyond
+    /// `p_filesz`. Overlapping matches are all reported.
+    fn find_bytes(&self, needle: &[u8]) -> Vec<u64> {
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let mut found: Vec<u64> = self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .flat_map(|seg| {
+                let vaddr = seg.phdr().vaddr();
+                seg.data()
+                    .windows(needle.len())
+                    .enumerate()
+                    .filter(|&(_, window)| window == needle)
+                    .map(move |(i, _)| vaddr + i as u64)
+            })
+            .collect();
+        found.sort();
+
+        found
+    }
+    /// Same as `find_bytes`, for the UTF-8 bytes of `s`. No terminating NUL is searched for, so
+    /// matches inside longer strings are reported too.
+    fn find_string(&self, s: &str) -> Vec<u64> {
+        self.find_bytes(s.as_bytes())
+    }
+    /// The thread-local storage template described by the `PT_TLS` segment, `None` when the
+    /// binary does not use TLS.
+    fn tls(&self) -> Option<TlsInfo> {
+        self.segments()
+            .into_iter()
+            .find(|seg| *seg.segment_type() == SegmentType::PT_TLS)
+            .map(|seg| TlsInfo {
+                image: seg.data(),
+                mem_size: seg.phdr().mem_size(),
+                align: seg.phdr().align(),
+            })
+    }
+    /// Contents of the section with the given name. `SHT_NOBITS` sections (`.bss`) have no
+    /// contents in the file and give an empty slice. With the `compression` feature,
+    /// `SHF_COMPRESSED` sections are returned decompressed when they use zlib, otherwise the
+    /// raw bytes (including the `ElfXX_Chdr` header) are returned. The feature also inflates
+    /// the legacy GNU `.zdebug_*` sections, which are found by their `.debug_*` name as well.
+    fn section_data(&self, name: &str) -> Option<Cow<[u8]>> {
+        let sec = match self.section(name) {
+            Some(sec) => sec,
+            #[cfg(feature = "compression")]
+            None if name.starts_with(".debug_") => self.section(&format!(".z{}", &name[1..]))?,
+            None => return None,
+        };
+        if *sec.section_type() == SectionType::SHT_NOBITS {
+            return Some(Cow::Borrowed(&[]));
+        }
+        #[cfg(feature = "compression")]
+        {
+            if sec.flags().contains(SectionFlag::SHF_COMPRESSED) {
+                if let Some(data) = decompress_section(sec.data(), self.is_64bit()) {
+                    return Some(Cow::Owned(data));
+                }
+            }
+            if sec.name().starts_with(".zdebug_") {
+                if let Some(data) = decompress_zdebug(sec.data()) {
+                    return Some(Cow::Owned(data));
+                }
+            }
+        }
+
+        Some(Cow::Borrowed(sec.data()))
+    }
+    /// Entries of the `.comment` section, usually the version strings of the compilers and linker
+    /// involved. Empty when there is no such section.
+    fn comment(&self) -> Vec<String> {
+        match self.section_data(".comment") {
+            Some(data) => data
+                .split(|&b| b == 0)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| String::from_utf8_lossy(entry).into_owned())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+    /// The notes of the file. Those of the `PT_NOTE` segments come first, then those only found
+    /// in `SHT_NOTE` sections, e.g. in relocatable objects or in sections not loaded. A note
+    /// present in both views is returned once, from its segment.
+    fn notes(&self) -> Vec<Note> {
+        let mut notes = self.notes_from_segments();
+        for note in self.notes_from_sections() {
+            if !notes.iter().any(|known| known.same_content(&note)) {
+                notes.push(note);
+            }
+        }
+
+        notes
+    }
+    /// The notes of the `PT_NOTE` segments, in program header order.
+    fn notes_from_segments(&self) -> Vec<Note> {
+        self.segments()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, seg)| *seg.segment_type() == SegmentType::PT_NOTE)
+            .flat_map(|(index, seg)| {
+                parse_notes(seg.data(), seg.phdr().align(), NoteOrigin::Segment(index))
+            })
+            .collect()
+    }
+    /// The notes of the `SHT_NOTE` sections, in section header order.
+    fn notes_from_sections(&self) -> Vec<Note> {
+        self.sections()
+            .into_iter()
+            .filter(|sec| *sec.section_type() == SectionType::SHT_NOTE)
+            .flat_map(|sec| {
+                parse_notes(sec.data(), sec.shdr().addr_align(), NoteOrigin::Section(sec.name()))
+            })
+            .collect()
+    }
+    /// General purpose registers of every thread of a core dump, from its `NT_PRSTATUS` notes.
+    /// The thread which received the fatal signal comes first. Only x86-64 is supported, other
+    /// machines and files without such notes give an empty result.
+    fn thread_registers(&self) -> Vec<Registers> {
+        if self.header().machine().ok() != Some(ElfMachine::X86_64) {
+            return Vec::new();
+        }
+
+        self.notes()
+            .iter()
+            .filter(|note| note.name == "CORE" && note.note_type == NT_PRSTATUS)
+            .filter_map(|note| parse_x86_64_prstatus(note.desc))
+            .map(Registers::X86_64)
+            .collect()
+    }
+    /// Name, command line and ids of the process a core dump comes from, from its `NT_PRPSINFO`
+    /// note. `None` if there is no such note.
+    fn process_info(&self) -> Option<ProcessInfo> {
+        let note = self.notes()
+            .into_iter()
+            .find(|note| note.name == "CORE" && note.note_type == NT_PRPSINFO)?;
+        parse_prpsinfo(note.desc, self.is_64bit())
+    }
+    /// Files mapped in the address space of the process a core dump comes from, e.g. the
+    /// executable and the shared libraries, from its `NT_FILE` note. Empty if there is no such
+    /// note.
+    fn mapped_files(&self) -> Vec<MappedFile> {
+        self.notes()
+            .into_iter()
+            .find(|note| note.name == "CORE" && note.note_type == NT_FILE)
+            .and_then(|note| parse_file_note(note.desc, self.is_64bit()))
+            .unwrap_or_default()
+    }
+    /// Pairs every PLT stub with the imported symbol it resolves to, using the `.rela.plt` (or
+    /// `.rel.plt`) relocations against the GOT. The stubs are assumed to follow the PLT header in
+    /// relocation order, which is how the linker lays them out. The stub layout is architecture
+    /// specific, only x86, x86-64, ARM and AArch64 are supported, others give an empty result.
+    fn plt_entries(&self) -> Vec<PltEntry> {
+        // (header size, stub size) of `.plt`
+        let (header_size, stub_size): (u64, u64) = match self.header().machine() {
+            Ok(ElfMachine::I386) | Ok(ElfMachine::X86_64) => (16, 16),
+            Ok(ElfMachine::AARCH64) => (32, 16),
+            Ok(ElfMachine::ARM) => (20, 12),
+            _ => return Vec::new(),
+        };
+        let relocs_section = match self.section(".rela.plt").or(self.section(".rel.plt")) {
+            Some(sec) => sec,
+            None => return Vec::new(),
+        };
+        // With IBT enabled, the stubs called by the code live in `.plt.sec` without any header
+        let (plt, header_size) = match self.section(".plt.sec") {
+            Some(sec) => (sec, 0),
+            None => match self.section(".plt") {
+                Some(sec) => (sec, header_size),
+                None => return Vec::new(),
+            },
+        };
+        let relocs = match self.relocations(relocs_section) {
+            Ok(relocs) => relocs,
+            Err(_) => return Vec::new(),
+        };
+        let symbols = linked_symbols(self, relocs_section);
+
+        relocs
+            .iter()
+            .enumerate()
+            .map(|(i, reloc)| PltEntry {
+                // `sh_addr` comes from the file, wrap like the address space does
+                address: plt.shdr()
+                    .address()
+                    .wrapping_add(header_size)
+                    .wrapping_add(stub_size.wrapping_mul(i as u64)),
+                got_address: reloc.offset(),
+                symbol: symbols
+                    .get(reloc.symbol_index() as usize)
+                    .map(|sym| sym.name().to_string())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+    /// The slots of `.got` and `.got.plt` as `(virtual address, value)` pairs, in that order.
+    /// The values are the ones in the file, the loader overwrites most of them. On x86 and
+    /// x86-64 the first three slots of `.got.plt` are reserved: the address of `_DYNAMIC`, then
+    /// the link map and the resolver filled in by the loader.
+    fn got_entries(&self) -> Vec<(u64, u64)> {
+        let size = self.pointer_size() as u64;
+        [".got", ".got.plt"]
+            .iter()
+            .filter_map(|name| self.section(name))
+            .flat_map(|sec| {
+                let address = sec.shdr().address();
+                pointers(self, sec.data())
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(i, value)| (address + i as u64 * size, value))
+            })
+            .collect()
+    }
+    /// Same as `got_entries`, with the symbol of the relocation (e.g. `R_X86_64_GLOB_DAT` or
+    /// `R_X86_64_JUMP_SLOT`) targeting each slot, looked up in every relocation section.
+    fn got_symbols(&self) -> Vec<GotEntry> {
+        // (relocated address, symbol name)
+        let mut targets = Vec::new();
+        for sec in self.sections() {
+            let relocs = self.relocations(sec).unwrap_or_default();
+            if relocs.is_empty() {
+                continue;
+            }
+            let symbols = linked_symbols(self, sec);
+            targets.extend(relocs
+                .iter()
+                .filter(|reloc| reloc.symbol_index() != 0)
+                .filter_map(|reloc| symbols
+                    .get(reloc.symbol_index() as usize)
+                    .map(|sym| (reloc.offset(), sym.name().to_string()))));
+        }
+
+        self.got_entries()
+            .into_iter()
+            .map(|(address, value)| GotEntry {
+                address: address,
+                value: value,
+                symbol: targets
+                    .iter()
+                    .find(|&&(target, _)| target == address)
+                    .map(|&(_, ref name)| name.clone()),
+            })
+            .collect()
+    }
+    /// Every entry of every relocation section, in file order, with its type name and the name
+    /// of its symbol looked up in the symbol table the section links to. Unknown machines get
+    /// `R_<number>` type names.
+    fn resolved_relocations(&self) -> Vec<ResolvedReloc> {
+        let machine = self.header().machine().ok();
+        let mut resolved = Vec::new();
+        for sec in self.sections() {
+            let relocs = self.relocations(sec).unwrap_or_default();
+            if relocs.is_empty() {
+                continue;
+            }
+            let symbols = linked_symbols(self, sec);
+            resolved.extend(relocs.iter().map(|reloc| ResolvedReloc {
+                section: sec.name().to_string(),
+                offset: reloc.offset(),
+                reloc_type: match machine {
+                    Some(machine) => reloc_type_name(machine, reloc.reloc_type()).into_owned(),
+                    None => format!("R_{}", reloc.reloc_type()),
+                },
+                symbol: match reloc.symbol_index() {
+                    0 => None,
+                    index => symbols.get(index as usize).map(|sym| sym.name().to_string()),
+                },
+                addend: reloc.addend(),
+            }));
+        }
+
+        resolved
+    }
+    /// The `.eh_frame_hdr` search table indexing the unwinding information, read from the
+    /// `PT_GNU_EH_FRAME` segment, or from the section when there is no such segment. `None` if
+    /// there is none or it cannot be decoded.
+    fn eh_frame_hdr(&self) -> Option<EhFrameHdr> {
+        let segment = self.segments()
+            .into_iter()
+            .find(|seg| *seg.segment_type() == SegmentType::PT_GNU_EH_FRAME);
+        let (data, address) = match segment {
+            Some(seg) => (seg.data(), seg.phdr().vaddr()),
+            None => {
+                let sec = self.section(".eh_frame_hdr")?;
+                (sec.data(), sec.shdr().address())
+            },
+        };
+
+        parse_eh_frame_hdr(data, address, self.is_64bit())
+    }
+    /// The ABI description of `.MIPS.abiflags`: ISA, register sizes and floating point ABI.
+    /// `None` for other machines than `ElfMachine::MIPS`, or without a valid section.
+    fn mips_abi_flags(&self) -> Option<MipsAbiFlags> {
+        if self.header().machine().ok() != Some(ElfMachine::MIPS) {
+            return None;
+        }
+
+        parse_abiflags(self.section(".MIPS.abiflags")?.data()).to_result().ok()
+    }
+    /// The register usage of `.reginfo`, only present in 32-bit MIPS files. `None` for other
+    /// machines than `ElfMachine::MIPS`, or without a valid section.
+    fn mips_reginfo(&self) -> Option<MipsRegInfo> {
+        if self.header().machine().ok() != Some(ElfMachine::MIPS) {
+            return None;
+        }
+
+        parse_reginfo(self.section(".reginfo")?.data()).to_result().ok()
+    }
+    /// The `.init_array` entries, the functions run at startup after `DT_INIT` such as the C++
+    /// static constructors. Read from the `SHT_INIT_ARRAY` section, or from `DT_INIT_ARRAY` and
+    /// `DT_INIT_ARRAYSZ` without section headers. With `RELA` relocations, some linkers leave the
+    /// slots for the loader to fill, they then read as 0.
+    fn init_array(&self) -> Vec<u64> {
+        pointer_array(self, SectionType::SHT_INIT_ARRAY, DynamicTag::DT_INIT_ARRAY,
+            DynamicTag::DT_INIT_ARRAYSZ)
+    }
+    /// Same as `init_array` for the `.fini_array` functions, run at exit in reverse order.
+    fn fini_array(&self) -> Vec<u64> {
+        pointer_array(self, SectionType::SHT_FINI_ARRAY, DynamicTag::DT_FINI_ARRAY,
+            DynamicTag::DT_FINI_ARRAYSZ)
+    }
+    /// The interpreter and the `DT_NEEDED`, `DT_RPATH` and `DT_RUNPATH` entries, whose strings
+    /// are read from the `DT_STRTAB` table. The colon separated search paths are split, `$ORIGIN`
+    /// and the like are left as is. Statically linked files give an empty `LoaderInfo`.
+    fn loader_info(&self) -> LoaderInfo {
+        let interp = self.segments()
+            .into_iter()
+            .find(|seg| *seg.segment_type() == SegmentType::PT_INTERP)
+            .map(|seg| seg.data())
+            .or_else(|| self.section(".interp").map(|sec| sec.data()));
+        let mut info = LoaderInfo {
+            interpreter: interp.map(|data| {
+                let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                String::from_utf8_lossy(&data[..end]).into_owned()
+            }),
+            ..LoaderInfo::default()
+        };
+        let dynamic = self.dynamic().unwrap_or_default();
+        let strtab = match (
+            dynamic_value(&dynamic, DynamicTag::DT_STRTAB),
+            dynamic_value(&dynamic, DynamicTag::DT_STRSZ),
+        ) {
+            // The table is in the file, a larger size is bogus and would only allocate zeros
+            (Some(address), Some(size)) if size <= self.file_size() => {
+                self.read_virtual(address, size as usize)
+            },
+            _ => None,
+        }.unwrap_or_default();
+        let strtab = StringTable(&strtab);
+        let string = |offset: u64| {
+            String::from_utf8_lossy(strtab.get_bytes(offset as u32).unwrap_or(&[])).into_owned()
+        };
+        for entry in dynamic.iter() {
+            match *entry.tag() {
+                MaybeKnown::Known(DynamicTag::DT_NEEDED) => {
+                    info.needed.push(string(entry.value()))
+                },
+                MaybeKnown::Known(DynamicTag::DT_RPATH) => {
+                    info.rpath.extend(string(entry.value()).split(':').map(String::from))
+                },
+                MaybeKnown::Known(DynamicTag::DT_RUNPATH) => {
+                    info.runpath.extend(string(entry.value()).split(':').map(String::from))
+                },
+                _ => {},
+            }
+        }
+
+        info
+    }
+    /// The string table section with the given name, e.g. `.shstrtab`, `.strtab` or `.dynstr`.
+    /// `None` if there is no such section or it is not a `SHT_STRTAB`.
+    fn string_table(&self, section_name: &str) -> Option<StringTable> {
+        let sec = self.section(section_name)?;
+        if *sec.section_type() != SectionType::SHT_STRTAB {
+            return None;
+        }
+
+        Some(StringTable(sec.data()))
+    }
+    /// The SysV `.hash` table of the dynamic symbols, `None` if there is none or it is malformed.
+    fn sysv_hash(&self) -> Option<SysvHash> {
+        let sec = self.sections()
+            .into_iter()
+            .find(|sec| *sec.section_type() == SectionType::SHT_HASH)?;
+        SysvHash::parse(sec.data(), linked_symbols(self, sec)).ok()
+    }
+    /// The `.gnu.hash` table of the dynamic symbols, `None` if there is none or it is malformed.
+    fn gnu_hash(&self) -> Option<GnuHash> {
+        let sec = self.sections()
+            .into_iter()
+            .find(|sec| *sec.section_type() == SectionType::SHT_GNU_HASH)?;
+        GnuHash::parse(sec.data(), self.is_64bit(), linked_symbols(self, sec)).ok()
+    }
+    /// The `DT_FLAGS` and `DT_FLAGS_1` entries of the dynamic section, empty when missing.
+    /// Unknown bits are kept.
+    fn dynamic_flags(&self) -> (DynamicFlag, DynamicFlag1) {
+        let mut flags = DynamicFlag::empty();
+        let mut flags_1 = DynamicFlag1::empty();
+        for entry in self.dynamic().unwrap_or_default() {
+            match *entry.tag() {
+                MaybeKnown::Known(DynamicTag::DT_FLAGS) => {
+                    flags = DynamicFlag::from_bits_retain(entry.value())
+                },
+                MaybeKnown::Known(DynamicTag::DT_FLAGS_1) => {
+                    flags_1 = DynamicFlag1::from_bits_retain(entry.value())
+                },
+                _ => {},
+            }
+        }
+
+        (flags, flags_1)
+    }
+    /// Whether this is a position independent executable. Those are `ET_DYN` like shared
+    /// libraries, but ask for an interpreter (`PT_INTERP`) or are marked with `DF_1_PIE`.
+    fn is_pie(&self) -> bool {
+        if self.header().elf_type().ok() != Some(ElfType::ET_DYN) {
+            return false;
+        }
+        let has_interp = self.segments()
+            .iter()
+            .any(|seg| *seg.segment_type() == SegmentType::PT_INTERP);
+        let pie_flag = self.dynamic_flags().1.contains(DynamicFlag1::DF_1_PIE);
+
+        has_interp || pie_flag
+    }
+    /// Whether this is a shared library, i.e. an `ET_DYN` file which is not a PIE.
+    fn is_shared_library(&self) -> bool {
+        self.header().elf_type().ok() == Some(ElfType::ET_DYN) && !self.is_pie()
+    }
+    /// Whether the symbol table has been stripped, i.e. there is no `SHT_SYMTAB` section. Note
+    /// that a stripped binary may still have `.dynsym` for dynamic linking.
+    fn is_stripped(&self) -> bool {
+        !self.sections()
+            .iter()
+            .any(|sec| *sec.section_type() == SectionType::SHT_SYMTAB)
+    }
+    /// Whether any `.debug_*` section is present, or any of the legacy compressed `.zdebug_*`
+    /// ones.
+    fn has_debug_info(&self) -> bool {
+        self.sections()
+            .iter()
+            .any(|sec| sec.name().starts_with(".debug_") || sec.name().starts_with(".zdebug_"))
+    }
+    /// The DWARF sections, `.debug_*`, keyed by name, ready to be handed to a DWARF parser like
+    /// `gimli`. With the `compression` feature, `SHF_COMPRESSED` sections are decompressed like
+    /// in `section_data`, and the legacy GNU `.zdebug_*` sections (a `ZLIB` magic and a big
+    /// endian size before a zlib stream) are inflated and listed under their `.debug_*` name.
+    /// Sections which are not decompressed keep their raw bytes and name.
+    fn debug_sections(&self) -> HashMap<Cow<str>, Cow<[u8]>> {
+        let mut sections = HashMap::new();
+        for sec in self.sections() {
+            let name = sec.name();
+            if name.starts_with(".debug_") {
+                if let Some(data) = self.section_data(name) {
+                    sections.insert(Cow::Borrowed(name), data);
+                }
+            } else if name.starts_with(".zdebug_") {
+                #[cfg(feature = "compression")]
+                {
+                    if let Some(data) = decompress_zdebug(sec.data()) {
+                        sections.insert(Cow::Owned(format!(".{}", &name[2..])), Cow::Owned(data));
+                        continue;
+                    }
+                }
+                sections.insert(Cow::Borrowed(name), Cow::Borrowed(sec.data()));
+            }
+        }
+
+        sections
+    }
+    /// Rebuilds the file from the parsed structures and appends it to `out`. The contents of the
+    /// segments and sections are laid out at their file offsets, then the ELF header, program
+    /// header table and section header table are encoded on top. Bytes covered by none of them
+    /// (alignment padding) are written as zeros, which is what linkers emit, so an unmodified
+    /// file produced by a regular toolchain comes out byte-identical. Contents or header table
+    /// entries starting past the end of the file are rejected with `SegmentOutOfBounds` or
+    /// `SectionOutOfBounds`, and files parsed with a `ParseOptions::section_filter` which left
+    /// sections out with `FilteredSections`. The header tables are parsed as contiguous arrays,
+    /// so an `e_phentsize` or `e_shentsize` other than the size of the class' header, which
+    /// could not be written back in place, is a `SizeMismatch`.
+    fn write(&self, out: &mut Vec<u8>) -> Result<(), RustepError> {
+        let hdr = self.header();
+        let segments = self.segments();
+        let sections = self.sections();
+        // The header table would be written short, under the original count
+        if sections.len() as u64 != self.section_count() {
+            Err(RustepError::FilteredSections {
+                kept: sections.len(),
+                count: self.section_count(),
+            })?
+        }
+        // Same check as `validate`
+        let (phdr_size, shdr_size) = if self.is_64bit() {
+            (mem::size_of::<Elf64_Phdr>() as u64, mem::size_of::<Elf64_Shdr>() as u64)
+        } else {
+            (mem::size_of::<Elf32_Phdr>() as u64, mem::size_of::<Elf32_Shdr>() as u64)
+        };
+        if hdr.phnum() > 0 && hdr.phentsize() != phdr_size {
+            Err(RustepError::SizeMismatch(phdr_size, hdr.phentsize()))?
+        }
+        if hdr.shnum() > 0 && hdr.shentsize() != shdr_size {
+            Err(RustepError::SizeMismatch(shdr_size, hdr.shentsize()))?
+        }
+        let file_size = self.file_size();
+        let mut image = vec![0u8; file_size as usize];
+        {
+            // Copies `bytes` at `offset`, growing the image when they run past its end. The end
+            // of the range is the error when `offset` is past the end of the file or overflows.
+            let mut put = |offset: u64, bytes: &[u8]| -> Result<(), u64> {
+                // Lenient parsing keeps headers pointing past the end of the file, with no data
+                if bytes.is_empty() {
+                    return Ok(());
+                }
+                let end = offset.checked_add(bytes.len() as u64).ok_or(u64::max_value())?;
+                if offset > file_size {
+                    return Err(end);
+                }
+                if (image.len() as u64) < end {
+                    image.resize(end as usize, 0);
+                }
+                image[offset as usize..end as usize].copy_from_slice(bytes);
+                Ok(())
+            };
+            let segment_error = |index: usize, end: u64| RustepError::SegmentOutOfBounds {
+                index: index,
+                end: end,
+                file_size: file_size,
+            };
+            let section_error = |index: usize, end: u64| RustepError::SectionOutOfBounds {
+                index: index,
+                end: end,
+                file_size: file_size,
+            };
+            // Offset of entry `index` of a header table
+            let entry = |table: u64, stride: u64, index: usize| {
+                stride.checked_mul(index as u64).and_then(|offset| offset.checked_add(table))
+            };
+
+            for (i, seg) in segments.iter().enumerate() {
+                put(seg.phdr().offset(), seg.data()).map_err(|end| segment_error(i, end))?;
+            }
+            for sec in sections.iter() {
+                // `SHT_NOBITS` has no file contents, its range belongs to whatever follows
+                if *sec.section_type() != SectionType::SHT_NOBITS {
+                    put(sec.shdr().offset(), sec.data())
+                        .map_err(|end| section_error(sec.index(), end))?;
+                }
+            }
+            let header = hdr.to_bytes();
+            put(0, &header).map_err(|_| RustepError::FileTooSmall {
+                needed: header.len(),
+                got: file_size as usize,
+            })?;
+            for (i, seg) in segments.iter().enumerate() {
+                let offset = entry(hdr.phoff(), hdr.phentsize(), i);
+                offset
+                    .ok_or(u64::max_value())
+                    .and_then(|offset| put(offset, &seg.phdr().to_bytes()))
+                    .map_err(|end| segment_error(i, end))?;
+            }
+            for (i, sec) in sections.iter().enumerate() {
+                let offset = entry(hdr.shoff(), hdr.shentsize(), i);
+                offset
+                    .ok_or(u64::max_value())
+                    .and_then(|offset| put(offset, &sec.shdr().to_bytes()))
+                    .map_err(|end| section_error(sec.index(), end))?;
+            }
+        }
+        out.extend_from_slice(&image);
+
+        Ok(())
+    }
+    /// Segments both writable and executable, violating W^X. Any type is reported, so a
+    /// `PT_GNU_STACK` asking for an executable stack shows up too.
+    fn writable_executable_segments(&self) -> Vec<&ElfSegment> {
+        self.segments()
+            .into_iter()
+            .filter(|seg| seg.is_writable() && seg.is_executable())
+            .collect()
+    }
+    /// Whether any segment is both writable and executable, see `writable_executable_segments`.
+    fn has_rwx_segment(&self) -> bool {
+        !self.writable_executable_segments().is_empty()
+    }
+    /// Whether every `PT_LOAD` segment satisfies `p_vaddr ≡ p_offset (mod p_align)`, which the
+    /// loader relies on to map the file page by page.
+    fn segment_alignment_ok(&self) -> bool {
+        self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .all(|seg| {
+                let phdr = seg.phdr();
+                phdr.align() <= 1 || phdr.vaddr() % phdr.align() == phdr.offset() % phdr.align()
+            })
+    }
+    /// Checks the header against the rest of the file. Parsing is lenient about fields it does
+    /// not need, so a file may parse fine and still be malformed, which is common with
+    /// deliberately crafted binaries. All problems found are returned instead of the first one.
+    fn validate(&self) -> Result<(), Vec<ValidationWarning>> {
+        let hdr = self.header();
+        let mut warnings = Vec::new();
+        // (program header size, section header size) of the class
+        let (phdr_size, shdr_size) = if self.is_64bit() {
+            (mem::size_of::<Elf64_Phdr>() as u64, mem::size_of::<Elf64_Shdr>() as u64)
+        } else {
+            (mem::size_of::<Elf32_Phdr>() as u64, mem::size_of::<Elf32_Shdr>() as u64)
+        };
+
+        if hdr.phnum() > 0 && hdr.phentsize() != phdr_size {
+            warnings.push(ValidationWarning::PhentsizeMismatch {
+                expected: phdr_size,
+                found: hdr.phentsize(),
+            });
+        }
+        if hdr.shnum() > 0 && hdr.shentsize() != shdr_size {
+            warnings.push(ValidationWarning::ShentsizeMismatch {
+                expected: shdr_size,
+                found: hdr.shentsize(),
+            });
+        }
+        if hdr.shnum() > 0
+            && hdr.shstrndx() >= hdr.shnum()
+            && hdr.shstrndx() != SHN_XINDEX as u64
+        {
+            warnings.push(ValidationWarning::ShstrndxOutOfRange {
+                index: hdr.shstrndx(),
+                count: hdr.shnum(),
+            });
+        }
+        for (i, seg) in self.segments().iter().enumerate() {
+            let phdr = seg.phdr();
+            let end = phdr.offset().saturating_add(phdr.file_size());
+            if end > self.file_size() {
+                warnings.push(ValidationWarning::SegmentOutOfBounds {
+                    index: i,
+                    end: end,
+                    file_size: self.file_size(),
+                });
+            }
+            let violation = segment_invariant_violation(
+                phdr.file_size(),
+                phdr.mem_size(),
+                phdr.align()
+            );
+            if let Some(reason) = violation {
+                warnings.push(ValidationWarning::InvalidSegment { index: i, reason: reason });
+            }
+        }
+        // The linker emits section contents in header order, `SHT_NOBITS` and empty sections
+        // take no room in the file and are skipped.
+        let mut previous_end = 0;
+        for sec in self.sections().into_iter().filter(|sec| sec.index() != 0) {
+            let shdr = sec.shdr();
+            if *sec.section_type() == SectionType::SHT_NOBITS || shdr.size() == 0 {
+                continue;
+            }
+            let end = shdr.offset().saturating_add(shdr.size());
+            if end > self.file_size() {
+                warnings.push(ValidationWarning::SectionOutOfBounds {
+                    index: sec.index(),
+                    end: end,
+                    file_size: self.file_size(),
+                });
+            }
+            if shdr.offset() < previous_end {
+                warnings.push(ValidationWarning::SectionOverlap {
+                    index: sec.index(),
+                    offset: shdr.offset(),
+                    previous_end: previous_end,
+                });
+            }
+            previous_end = end;
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+    /// Sections whose contents extend past the end of the file, e.g. in a truncated download.
+    /// Their `data` only holds the bytes that are present. Only found in files parsed without
+    /// `ParseOptions::strict`, which rejects them.
+    fn truncated_sections(&self) -> Vec<&ElfSection> {
+        self.sections()
+            .into_iter()
+            .filter(|sec| *sec.section_type() != SectionType::SHT_NOBITS)
+            .filter(|sec| (sec.data().len() as u64) < sec.shdr().size())
+            .collect()
+    }
+    /// File ranges `[start, end)` belonging to no structure: neither the ELF header, the
+    /// program or section header tables, a section nor a segment. Alignment padding shows up
+    /// here, as does data appended after the last structure or hidden between them. Sorted by
+    /// offset.
+    fn uncovered_ranges(&self) -> Vec<(u64, u64)> {
+        let hdr = self.header();
+        let mut used = vec![
+            (0, hdr.ehsize()),
+            (hdr.phoff(), hdr.phoff() + self.raw_program_headers().len() as u64),
+            (hdr.shoff(), hdr.shoff() + self.raw_section_headers().len() as u64),
+        ];
+        used.extend(self.sections()
+            .into_iter()
+            .filter(|sec| *sec.section_type() != SectionType::SHT_NOBITS)
+            .map(|sec| (sec.shdr().offset(), sec.shdr().offset() + sec.data().len() as u64)));
+        used.extend(self.segments()
+            .into_iter()
+            .map(|seg| (seg.phdr().offset(), seg.phdr().offset() + seg.data().len() as u64)));
+        used.sort();
+
+        let mut gaps = Vec::new();
+        let mut covered = 0;
+        for (start, end) in used.into_iter().filter(|&(start, end)| start < end) {
+            if start > covered {
+                gaps.push((covered, start));
+            }
+            covered = cmp::max(covered, end);
+        }
+        if covered < self.file_size() {
+            gaps.push((covered, self.file_size()));
+        }
+
+        gaps
+    }
+    /// SHA-256 of the loadable contents, to tell whether two binaries carry the same code and
+    /// data regardless of their section layout or metadata. The hashed bytes are the file
+    /// contents (`p_filesz` bytes from `p_offset`) of every `PT_LOAD` segment, concatenated in
+    /// ascending `p_vaddr` order, segments at the same address keeping their program header
+    /// order. Nothing else is included, in particular not the zero-filled tail of the segments
+    /// (`p_memsz` beyond `p_filesz`). Note that the first `PT_LOAD` usually maps the ELF and
+    /// program headers, so those are part of the hash. Only available with the `hash` feature.
+    #[cfg(feature = "hash")]
+    fn content_hash(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let mut loads: Vec<&ElfSegment> = self.segments()
+            .into_iter()
+            .filter(|seg| *seg.segment_type() == SegmentType::PT_LOAD)
+            .collect();
+        // `sort_by_key` is stable
+        loads.sort_by_key(|seg| seg.phdr().vaddr());
+
+        let mut hasher = Sha256::new();
+        for seg in loads {
+            hasher.update(seg.data());
+        }
+
+        hasher.finalize().into()
+    }
+    /// Dump header, segments and sections as a JSON string. Enum values are written as their
+    /// symbolic names and flags as arrays of flag names. Only available with the `serde` feature.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> String {
+        // Serializing into a `String` has no I/O involved, all our fields are serializable.
+        ::serde_json::to_string(&ElfFormatSerializer(self)).unwrap()
+    }
+}
+
+/// Byte order of a file, see `ElfFormat::endianness`
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Thread-local storage template, see `ElfFormat::tls`. Every thread gets a block of `mem_size`
+/// bytes aligned to `align`, starting with a copy of `image` (`.tdata`) followed by zeros
+/// (`.tbss`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TlsInfo<'a> {
+    /// Initialization image, the file contents of the `PT_TLS` segment
+    pub image: &'a [u8],
+    /// Total size of the TLS block, `p_memsz`
+    pub mem_size: u64,
+    /// Alignment of the TLS block, `p_align`
+    pub align: u64,
+}
+
+/// A `PT_LOAD` segment as mapped in memory, see `ElfFormat::memory_map`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemRegion {
+    /// Start address, `p_vaddr`
+    pub vaddr: u64,
+    /// Size in memory, `p_memsz`
+    pub size: u64,
+    /// Where the contents start in the file, `p_offset`
+    pub file_offset: u64,
+    /// Size of the contents in the file, `p_filesz`, the rest of the region is zero filled
+    pub file_size: u64,
+    /// Protection in the `/proc/<pid>/maps` format, e.g. `r-xp`
+    pub perms: String,
+}
+
+/// What the dynamic loader needs to resolve the dependencies of a file, see
+/// `ElfFormat::loader_info`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoaderInfo {
+    /// Path of the program interpreter (`PT_INTERP`), the dynamic loader itself
+    pub interpreter: Option<String>,
+    /// `DT_RPATH` search paths, in order
+    pub rpath: Vec<String>,
+    /// `DT_RUNPATH` search paths, in order
+    pub runpath: Vec<String>,
+    /// `DT_NEEDED` library names, in load order
+    pub needed: Vec<String>,
+}
+
+/// The ELF header fields needed to triage a file, see `Executable::parse_headers_only`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HeaderSummary {
+    /// Whether this is an `ELFCLASS64` file
+    pub is_64bit: bool,
+    pub endianness: Endianness,
+    pub elf_type: ElfType,
+    /// `e_machine`, unknown machines keep their raw value
+    pub machine: MaybeKnown<ElfMachine>,
+    /// Entry point, `e_entry`
+    pub entry: u64,
+    /// Processor specific flags, `e_flags`
+    pub flags: u32,
+    /// Number of program headers, `e_phnum`
+    pub segment_count: usize,
+    /// Number of section headers, `e_shnum`
+    pub section_count: usize,
+}
+
+/// An inconsistency found by `ElfFormat::validate`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationWarning {
+    /// `e_phentsize` is not the program header size of the class
+    PhentsizeMismatch { expected: u64, found: u64 },
+    /// `e_shentsize` is not the section header size of the class
+    ShentsizeMismatch { expected: u64, found: u64 },
+    /// `e_shstrndx` does not point to an existing section header
+    ShstrndxOutOfRange { index: u64, count: u64 },
+    /// The file range of the segment at `index` ends past the end of the file
+    SegmentOutOfBounds { index: usize, end: u64, file_size: u64 },
+    /// The segment at `index` has more file than memory contents, or an alignment which is not
+    /// a power of two
+    InvalidSegment { index: usize, reason: &'static str },
+    /// The section at `index` starts before the end of the previous section in the file
+    SectionOverlap { index: usize, offset: u64, previous_end: u64 },
+    /// The file range of the section at `index` ends past the end of the file, its contents
+    /// are cut
+    SectionOutOfBounds { index: usize, end: u64, file_size: u64 },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationWarning::PhentsizeMismatch { expected, found } =>
+                write!(f, "e_phentsize is {}, expected {}", found, expected),
+            ValidationWarning::ShentsizeMismatch { expected, found } =>
+                write!(f, "e_shentsize is {}, expected {}", found, expected),
+            ValidationWarning::ShstrndxOutOfRange { index, count } =>
+                write!(f, "e_shstrndx {} is out of range of {} sections", index, count),
+            ValidationWarning::SegmentOutOfBounds { index, end, file_size } =>
+                write!(f, "segment {} ends at {:#x}, past the end of file {:#x}", index, end, file_size),
+            ValidationWarning::InvalidSegment { index, reason } =>
+                write!(f, "segment {} is invalid, {}", index, reason),
+            ValidationWarning::SectionOverlap { index, offset, previous_end } =>
+                write!(f, "section {} starts at {:#x}, before the end of the previous section {:#x}",
+                       index, offset, previous_end),
+            ValidationWarning::SectionOutOfBounds { index, end, file_size } =>
+                write!(f, "section {} ends at {:#x}, past the end of file {:#x}", index, end, file_size),
+        }
+    }
+}
+
+/// Elf file format 32-bit version
+pub struct Elf32<'a> {
+    header: Elf32_Ehdr,
+    elf_type: ElfType,
+    segments: Vec<ElfSegment32<'a>>,
+    sections: Vec<ElfSection32<'a>>,
+    symbols: Vec<ElfSymbol32<'a>>,
+    dynamic_symbols: Vec<ElfSymbol32<'a>>,
+    file_size: u64,
+    section_count: u64,
+    raw_program_headers: Cow<'a, [u8]>,
+    raw_section_headers: Cow<'a, [u8]>,
+    overlay: Cow<'a, [u8]>,
+}
+
+
+/// Elf file format 64-bit version
+pub struct Elf64<'a> {
+    header: Elf64_Ehdr,
+    elf_type: ElfType,
+    segments: Vec<ElfSegment64<'a>>,
+    sections: Vec<ElfSection64<'a>>,
+    symbols: Vec<ElfSymbol64<'a>>,
+    dynamic_symbols: Vec<ElfSymbol64<'a>>,
+    file_size: u64,
+    section_count: u64,
+    raw_program_headers: Cow<'a, [u8]>,
+    raw_section_headers: Cow<'a, [u8]>,
+    overlay: Cow<'a, [u8]>,
+}
+
+/// Whether the symbol takes part in dynamic linking: a global, weak or unique binding and not a
+/// `STT_SECTION` or `STT_FILE` entry.
+fn is_linkable_symbol(symbol: &ElfSymbol) -> bool {
+    let binding_ok = match symbol.binding() {
+        MaybeKnown::Known(SymbolBinding::STB_GLOBAL)
+        | MaybeKnown::Known(SymbolBinding::STB_WEAK)
+        | MaybeKnown::Known(SymbolBinding::STB_GNU_UNIQUE) => true,
+        _ => false,
+    };
+    let symbol_type = symbol.sym_type();
+    let type_ok = symbol_type != SymbolType::STT_SECTION && symbol_type != SymbolType::STT_FILE;
+
+    binding_ok && type_ok
+}
+
+/// The `nm` type code of a symbol of `elf`: the kind of section it is defined in, or the special
+/// case of its binding or type, uppercase for global symbols.
+fn nm_type_code<T: ElfFormat + ?Sized>(symbol: &ElfSymbol, elf: &T) -> char {
+    let binding = symbol.binding();
+    let is_weak = binding == SymbolBinding::STB_WEAK;
+    let is_object = symbol.sym_type() == SymbolType::STT_OBJECT;
+    let code = match symbol.section_index() {
+        SHN_UNDEF if is_weak => return if is_object { 'v' } else { 'w' },
+        SHN_UNDEF => return 'U',
+        _ if is_weak => return if is_object { 'V' } else { 'W' },
+        _ if binding == SymbolBinding::STB_GNU_UNIQUE => return 'u',
+        _ if symbol.is_ifunc() => return 'i',
+        SHN_ABS => 'a',
+        SHN_COMMON => 'c',
+        index => match elf.section_by_index(index as usize) {
+            Some(sec) => {
+                let flags = sec.flags();
+                if !flags.contains(SectionFlag::SHF_ALLOC) {
+                    'n'
+                } else if flags.contains(SectionFlag::SHF_EXECINSTR) {
+                    't'
+                } else if *sec.section_type() == SectionType::SHT_NOBITS {
+                    'b'
+                } else if flags.contains(SectionFlag::SHF_WRITE) {
+                    'd'
+                } else {
+                    'r'
+                }
+            },
+            None => '?',
+        },
+    };
+
+    if binding == SymbolBinding::STB_LOCAL { code } else { code.to_ascii_uppercase() }
+}
+
+/// What is wrong with a segment of these sizes and alignment, if anything: the file contents
+/// must fit in memory and the alignment be 0 or a power of two.
+fn segment_invariant_violation(file_size: u64, mem_size: u64, align: u64) -> Option<&'static str> {
+    if file_size > mem_size {
+        Some("p_filesz is larger than p_memsz")
+    } else if align != 0 && !align.is_power_of_two() {
+        Some("p_align is not a power of two")
+    } else {
+        None
+    }
+}
+
+/// Value of the first dynamic entry with the given tag.
+fn dynamic_value(dynamic: &[DynamicEntry], tag: DynamicTag) -> Option<u64> {
+    dynamic
+        .iter()
+        .find(|entry| *entry.tag() == tag)
+        .map(|entry| entry.value())
+}
+
+/// The function pointers of the `section_type` section, or when there is none of the range given
+/// by the `tag` and `size_tag` dynamic entries, decoded as words of the file's class and
+/// endianness. A trailing partial word is dropped.
+fn pointer_array<T: ElfFormat + ?Sized>(
+    elf: &T,
+    section_type: SectionType,
+    tag: DynamicTag,
+    size_tag: DynamicTag,
+) -> Vec<u64> {
+    let section = elf.sections()
+        .into_iter()
+        .find(|sec| *sec.section_type() == section_type);
+    let data = match section {
+        Some(sec) => Cow::Borrowed(sec.data()),
+        None => {
+            let dynamic = elf.dynamic().unwrap_or_default();
+            let data = match (dynamic_value(&dynamic, tag), dynamic_value(&dynamic, size_tag)) {
+                // As in `loader_info`, the array cannot be larger than the file
+                (Some(address), Some(size)) if size <= elf.file_size() => {
+                    elf.read_virtual(address, size as usize)
+                },
+                _ => None,
+            };
+            match data {
+                Some(data) => Cow::Owned(data),
+                None => return Vec::new(),
+            }
+        },
+    };
+
+    pointers(elf, &data)
+}
+
+/// Decodes `data` as pointers of the file's size and endianness. A trailing partial pointer is
+/// dropped.
+fn pointers<T: ElfFormat + ?Sized>(elf: &T, data: &[u8]) -> Vec<u64> {
+    let big = elf.endianness() == Endianness::Big;
+    let word_size = elf.pointer_size();
+
+    data.chunks(word_size)
+        .filter(|word| word.len() == word_size)
+        .map(|word| {
+            let push = |value: u64, &byte: &u8| value << 8 | byte as u64;
+            if big {
+                word.iter().fold(0, push)
+            } else {
+                word.iter().rev().fold(0, push)
+            }
+        })
+        .collect()
+}
+
+/// The `size` bytes at `offset` in `input`, cut at the end of `input`, along with the end offset
+/// they were supposed to reach.
+fn file_range(input: &[u8], offset: u64, size: u64) -> (&[u8], u64) {
+    let end = offset.saturating_add(size);
+    let len = input.len() as u64;
+
+    (&input[cmp::min(offset, len) as usize..cmp::min(end, len) as usize], end)
+}
+
+/// The symbol table a relocation (or hash) section refers to through its `sh_link`.
+fn linked_symbols<'a, T: ElfFormat + ?Sized>(elf: &'a T, section: &ElfSection) -> Vec<&'a ElfSymbol> {
+    let link = section.shdr().link() as usize;
+    match elf.section_by_index(link).map(|sec| *sec.section_type()) {
+        Some(MaybeKnown::Known(SectionType::SHT_DYNSYM)) => elf.dynamic_symbols(),
+        Some(MaybeKnown::Known(SectionType::SHT_SYMTAB)) => elf.symbols(),
+        _ => Vec::new(),
+    }
+}
+
+/// Same rules as binutils' `ELF_SECTION_IN_SEGMENT_STRICT`, which `readelf` uses for its
+/// mapping.
+fn section_in_segment(section: &ElfSection, segment: &ElfSegment) -> bool {
+    let shdr = section.shdr();
+    let phdr = segment.phdr();
+    let seg_type = *segment.segment_type();
+    let is_tls = section.is_tls();
+    let is_alloc = section.is_alloc();
+    let is_nobits = *section.section_type() == SectionType::SHT_NOBITS;
+
+    // Only `PT_TLS` holds TLS sections beside the segments they are loaded with
+    let tls_allowed = if is_tls {
+        seg_type == SegmentType::PT_LOAD
+            || seg_type == SegmentType::PT_TLS
+            || seg_type == SegmentType::PT_GNU_RELRO
+    } else {
+        seg_type != SegmentType::PT_TLS && seg_type != SegmentType::PT_PHDR
+    };
+    // `.tbss` takes no room in the other segments, the next section starts at the same address
+    if !tls_allowed || (is_tls && is_nobits && seg_type != SegmentType::PT_TLS) {
+        return false;
+    }
+    let needs_alloc = seg_type == SegmentType::PT_LOAD
+        || seg_type == SegmentType::PT_DYNAMIC
+        || seg_type == SegmentType::PT_GNU_EH_FRAME
+        || seg_type == SegmentType::PT_GNU_STACK
+        || seg_type == SegmentType::PT_GNU_RELRO;
+    if needs_alloc && !is_alloc {
+        return false;
+    }
+
+    let in_file = is_nobits || (
+        shdr.offset() >= phdr.offset()
+            && shdr.offset() - phdr.offset() < phdr.file_size()
+            && shdr.size() <= phdr.file_size() - (shdr.offset() - phdr.offset())
+    );
+    let in_memory = !is_alloc || (
+        shdr.address() >= phdr.vaddr()
+            && shdr.address() - phdr.vaddr() < phdr.mem_size()
+            && shdr.size() <= phdr.mem_size() - (shdr.address() - phdr.vaddr())
+    );
+    if !in_file || !in_memory {
+        return false;
+    }
+
+    // Empty sections on the boundaries of `PT_DYNAMIC` and `PT_NOTE` are not part of them
+    if (seg_type == SegmentType::PT_DYNAMIC || seg_type == SegmentType::PT_NOTE)
+        && shdr.size() == 0
+        && phdr.mem_size() != 0
+    {
+        let file_inside = is_nobits || (
+            shdr.offset() > phdr.offset() && shdr.offset() - phdr.offset() < phdr.file_size()
+        );
+        let memory_inside = !is_alloc || (
+            shdr.address() > phdr.vaddr() && shdr.address() - phdr.vaddr() < phdr.mem_size()
+        );
+        return file_inside && memory_inside;
+    }
+
+    true
+}
+
+/// Inflates the contents of a `SHF_COMPRESSED` section, `None` if the compression is not zlib or
+/// the data is corrupted.
+#[cfg(feature = "compression")]
+fn decompress_section(data: &[u8], is_64: bool) -> Option<Vec<u8>> {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    let compression = le_u32(data).to_result().ok()?;
+    // `ch_size` follows `ch_type` in `Elf32_Chdr`, and `ch_reserved` in `Elf64_Chdr`
+    let (size, header_size) = if is_64 {
+        (le_u64(data.get(8..)?).to_result().ok()?, mem::size_of::<Elf64_Chdr>())
+    } else {
+        (le_u32(data.get(4..)?).to_result().ok()? as u64, mem::size_of::<Elf32_Chdr>())
+    };
+    // ELFCOMPRESS_ZLIB
+    if compression != 1 {
+        return None;
+    }
+    // `ch_size` comes from the file, so it only bounds the output and the allocation hint
+    let mut decompressed = Vec::with_capacity(cmp::min(size, data.len() as u64 * 16) as usize);
+    ZlibDecoder::new(data.get(header_size..)?)
+        .take(size.saturating_add(1))
+        .read_to_end(&mut decompressed)
+        .ok()?;
+    if decompressed.len() as u64 != size {
+        return None;
+    }
+
+    Some(decompressed)
+}
+
+/// Inflates the contents of a legacy `.zdebug_*` section: `ZLIB`, the decompressed size as a big
+/// endian `u64`, then the zlib stream. `None` without the header or if the data is corrupted.
+#[cfg(feature = "compression")]
+fn decompress_zdebug(data: &[u8]) -> Option<Vec<u8>> {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    if !data.starts_with(b"ZLIB") {
+        return None;
+    }
+    let size = be_u64(data.get(4..)?).to_result().ok()?;
+    let mut decompressed = Vec::with_capacity(cmp::min(size, data.len() as u64 * 16) as usize);
+    ZlibDecoder::new(data.get(12..)?).read_to_end(&mut decompressed).ok()?;
+    if decompressed.len() as u64 != size {
+        return None;
+    }
+
+    Some(decompressed)
+}
+
+/// Shared implementation of `ElfFormat::format_section_table`, `address_width` is the number of
+/// hex digits used for addresses.
+fn section_table(sections: &[&ElfSection], address_width: usize) -> String {
+    let name_width = sections
+        .iter()
+        .map(|s| s.name().len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut table = format!(
+        "  [Nr] {:<nw$} {:<15} {:<aw$} {:<8} {:<8} ES Flg Lk Inf Al\n",
+        "Name",
+        "Type",
+        "Address",
+        "Off",
+        "Size",
+        nw = name_width,
+        aw = address_width
+    );
+    for sec in sections.iter() {
+        let shdr = sec.shdr();
+        let section_type = sec.section_type().to_string();
+        table.push_str(&format!(
+            "  [{:>2}] {:<nw$} {:<15} {:0aw$x} {:08x} {:08x} {:02x} {:>3} {:>2} {:>3} {:>2}\n",
+            sec.index(),
+            sec.name(),
+            section_type.trim_start_matches("SHT_"),
+            shdr.address(),
+            shdr.offset(),
+            shdr.size(),
+            shdr.entry_size(),
+            section_flags_short(sec.flags()),
+            shdr.link(),
+            shdr.info(),
+            shdr.addr_align(),
+            nw = name_width,
+            aw = address_width
+        ));
+    }
+
+    table
+}
+
+/// Elf file format 32-bit version owning all its data, it does not borrow the input buffer.
+pub type Elf32Owned = Elf32<'static>;
+
+/// Elf file format 64-bit version owning all its data, it does not borrow the input buffer.
+pub type Elf64Owned = Elf64<'static>;
+
+macro_rules! define_elf_to_owned {
+    {
+        $result: ident,
+        $section: ident,
+        $segment: ident,
+        $symbol: ident
+    } => {
+        impl<'a> $result<'a> {
+            /// Copies all section and segment data out of the input buffer, so the result can
+            /// outlive it.
+            pub fn to_owned(&self) -> $result<'static> {
+                let segments = self.segments
+                    .iter()
+                    .map(|s| $segment {
+                        phdr: s.phdr,
+                        segment_type: s.segment_type,
+                        flags: s.flags,
+                        data: Cow::Owned(s.data.to_vec()),
+                    })
+                    .collect();
+                let sections = self.sections
+                    .iter()
+                    .map(|s| $section {
+                        shdr: s.shdr,
+                        section_type: s.section_type,
+                        flags: s.flags,
+                        name: s.name.clone(),
+                        index: s.index,
+                        data: Cow::Owned(s.data.to_vec()),
+                    })
+                    .collect();
+                let copy_symbol = |s: &$symbol| $symbol {
+                    sym: s.sym,
+                    name: Cow::Owned(s.name.clone().into_owned()),
+                };
+
+                $result {
+                    header: self.header,
+                    elf_type: self.elf_type,
+                    segments: segments,
+                    sections: sections,
+                    symbols: self.symbols.iter().map(&copy_symbol).collect(),
+                    dynamic_symbols: self.dynamic_symbols.iter().map(&copy_symbol).collect(),
+                    file_size: self.file_size,
+                    section_count: self.section_count,
+                    raw_program_headers: Cow::Owned(self.raw_program_headers.to_vec()),
+                    raw_section_headers: Cow::Owned(self.raw_section_headers.to_vec()),
+                    overlay: Cow::Owned(self.overlay.to_vec()),
+                }
+            }
+        }
+    }
+}
+
+define_elf_to_owned!{Elf32, ElfSection32, ElfSegment32, ElfSymbol32}
+define_elf_to_owned!{Elf64, ElfSection64, ElfSegment64, ElfSymbol64}
+
+impl<'a> ElfFormat for Elf32<'a> {
+    fn header(&self) -> &ElfHeader {
+        &self.header
+    }
+
+    fn segments(&self) -> Vec<&ElfSegment> {
+        let mut v = Vec::new();
+        for elem in self.segments.iter() {
+            v.push(elem as &ElfSegment);
+        }
+
+        v
+    }
+
+    fn sections(&self) -> Vec<&ElfSection> {
+        let mut v = Vec::new();
+        for elem in self.sections.iter() {
+            v.push(elem as &ElfSection);
+        }
+
+        v
+    }
+
+    fn format_section_table(&self) -> String {
+        section_table(&self.sections(), 8)
+    }
+
+    fn symbols(&self) -> Vec<&ElfSymbol> {
+        self.symbols.iter().map(|s| s as &ElfSymbol).collect()
+    }
+
+    fn dynamic_symbols(&self) -> Vec<&ElfSymbol> {
+        self.dynamic_symbols.iter().map(|s| s as &ElfSymbol).collect()
+    }
+
+    fn relocations(&self, section: &ElfSection) -> Result<Vec<ElfRelocation>, RustepError> {
+        match *section.section_type() {
+            MaybeKnown::Known(SectionType::SHT_REL) => parse_relocations32(section.data(), false),
+            MaybeKnown::Known(SectionType::SHT_RELA) => parse_relocations32(section.data(), true),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn file_size(&self) -> u64 {
+        self.file_size
+    }
+
+    fn section_count(&self) -> u64 {
+        self.section_count
+    }
+
+    fn raw_program_headers(&self) -> &[u8] {
+        &self.raw_program_headers
+    }
+
+    fn raw_section_headers(&self) -> &[u8] {
+        &self.raw_section_headers
+    }
+
+    fn overlay(&self) -> Option<&[u8]> {
+        if self.overlay.is_empty() {
+            None
+        } else {
+            Some(&self.overlay)
+        }
+    }
+
+    fn is_64bit(&self) -> bool {
+        false
+    }
+
+    fn dynamic(&self) -> Result<Vec<DynamicEntry>, RustepError> {
+        match self.segments.iter().find(|seg| seg.segment_type == SegmentType::PT_DYNAMIC) {
+            Some(seg) => parse_dynamic32(&seg.data),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl<'a> ElfFormat for Elf64<'a> {
+    fn header(&self) -> &ElfHeader {
+        &self.header
+    }
+
+    fn segments(&self) -> Vec<&ElfSegment> {
+        let mut v = Vec::new();
+        for elem in self.segments.iter() {
+            v.push(elem as &ElfSegment);
+        }
+
+        v
+    }
+
+    fn sections(&self) -> Vec<&ElfSection> {
+        let mut v = Vec::new();
+        for elem in self.sections.iter() {
+            v.push(elem as &ElfSection);
+        }
+
+        v
+    }
+
+    fn format_section_table(&self) -> String {
+        section_table(&self.sections(), 16)
+    }
+
+    fn symbols(&self) -> Vec<&ElfSymbol> {
+        self.symbols.iter().map(|s| s as &ElfSymbol).collect()
+    }
+
+    fn dynamic_symbols(&self) -> Vec<&ElfSymbol> {
+        self.dynamic_symbols.iter().map(|s| s as &ElfSymbol).collect()
+    }
+
+    fn relocations(&self, section: &ElfSection) -> Result<Vec<ElfRelocation>, RustepError> {
+        match *section.section_type() {
+            MaybeKnown::Known(SectionType::SHT_REL) => parse_relocations64(section.data(), false),
+            MaybeKnown::Known(SectionType::SHT_RELA) => parse_relocations64(section.data(), true),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn file_size(&self) -> u64 {
+        self.file_size
+    }
+
+    fn section_count(&self) -> u64 {
+        self.section_count
+    }
+
+    fn raw_program_headers(&self) -> &[u8] {
+        &self.raw_program_headers
+    }
+
+    fn raw_section_headers(&self) -> &[u8] {
+        &self.raw_section_headers
+    }
+
+    fn overlay(&self) -> Option<&[u8]> {
+        if self.overlay.is_empty() {
+            None
+        } else {
+            Some(&self.overlay)
+        }
+    }
+
+    fn is_64bit(&self) -> bool {
+        true
+    }
+
+    fn dynamic(&self) -> Result<Vec<DynamicEntry>, RustepError> {
+        match self.segments.iter().find(|seg| seg.segment_type == SegmentType::PT_DYNAMIC) {
+            Some(seg) => parse_dynamic64(&seg.data),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Executable<'a>> for &'a ElfFormat {
+    type Error = RustepError;
+
+    /// Tries to convert an [`Executable`](../executable/enum.Executable.html) reference to an
+    /// [`ElfFormat` trait object](../elf/trait.ElfFormat.html)
+    fn try_from(value: &'a Executable) -> Result<&'a ElfFormat, RustepError> {
+        match *value {
+            Executable::Elf32(ref elf) => Ok(elf as &ElfFormat),
+            Executable::Elf64(ref elf) => Ok(elf as &ElfFormat),
+            _ => Err(RustepError::NotElf)?,
+        }
+    } 
+}
+
+/// parses input byes to executable
+pub fn parse_elf(input: &[u8]) -> Result<Executable, RustepError> {
+    parse_elf_with(input, &ParseOptions::default())
+}
+
+/// Same as `parse_elf`, with control over what is parsed and how strictly.
+pub fn parse_elf_with<'a>(input: &'a [u8], options: &ParseOptions) -> Result<Executable<'a>, RustepError> {
+    // Without the class byte the smaller header is the best bound we can give
+    if input.len() <= EI_CLASS as usize {
+        Err(RustepError::FileTooSmall {
+            needed: mem::size_of::<Elf32_Ehdr>(),
+            got: input.len(),
+        })?
+    }
+    let elf_class = nom_try!(parse_elf_class(input), 0, "ELF identification") as u32;
+    match elf_class {
+        ELFCLASS32 => parse_elf32_with(input, options),
+        ELFCLASS64 => parse_elf64_with(input, options),
+        ELFCLASSNONE => Err(RustepError::InvalidElfClass)?,
+        val => Err(RustepError::UnsupportedElfClass(val as u8))?,
+    }
+}
+
+/// Parses the ELF header and checks that both header tables are within `input`, without looking
+/// at the contents of the sections and segments. See `Executable::parse_headers_only`.
+pub(crate) fn parse_elf_headers(input: &[u8]) -> Result<HeaderSummary, RustepError> {
+    macro_rules! summarize {
+        ($header_parser: ident, $segment_parser: ident, $section_parser: ident, $raw_header: ident) => {{
+            if input.len() < mem::size_of::<$raw_header>() {
+                Err(RustepError::FileTooSmall {
+                    needed: mem::size_of::<$raw_header>(),
+                    got: input.len(),
+                })?
+            }
+            let hdr = nom_try!($header_parser(input), 0, "ELF header");
+            if hdr.e_phnum != 0 {
+                nom_try!(preceded!(
+                    input,
+                    take!(hdr.e_phoff),
+                    count!(call!($segment_parser), hdr.e_phnum as usize)
+                ), hdr.e_phoff, "program header table");
+            }
+            if hdr.e_shnum != 0 {
+                nom_try!(preceded!(
+                    input,
+                    take!(hdr.e_shoff),
+                    count!(call!($section_parser), hdr.e_shnum as usize)
+                ), hdr.e_shoff, "section header table");
+            }
+
+            HeaderSummary {
+                is_64bit: hdr.e_ident[EI_CLASS as usize] as u32 == ELFCLASS64,
+                endianness: match hdr.e_ident[EI_DATA as usize] as u32 {
+                    ELFDATA2MSB => Endianness::Big,
+                    _ => Endianness::Little,
+                },
+                elf_type: FromPrimitive::from_u16(hdr.e_type)
+                    .ok_or(RustepError::ElfType(hdr.e_type as u64))?,
+                machine: MaybeKnown::from_u32(hdr.e_machine as u32),
+                entry: hdr.e_entry as u64,
+                flags: hdr.e_flags,
+                segment_count: hdr.e_phnum as usize,
+                section_count: hdr.e_shnum as usize,
+            }
+        }}
+    }
+
+    if input.len() <= EI_CLASS as usize {
+        Err(RustepError::FileTooSmall {
+            needed: mem::size_of::<Elf32_Ehdr>(),
+            got: input.len(),
+        })?
+    }
+    let elf_class = nom_try!(parse_elf_class(input), 0, "ELF identification") as u32;
+    Ok(match elf_class {
+        ELFCLASS32 => summarize!(
+            parse_elf_header32,
+            parse_elf_prog_header32,
+            parse_elf_section_header32,
+            Elf32_Ehdr
+        ),
+        ELFCLASS64 => summarize!(
+            parse_elf_header64,
+            parse_elf_prog_header64,
+            parse_elf_section_header64,
+            Elf64_Ehdr
+        ),
+        ELFCLASSNONE => Err(RustepError::InvalidElfClass)?,
+        val => Err(RustepError::UnsupportedElfClass(val as u8))?,
+    })
+}
+
+macro_rules! define_elf_parser {
+    {
+        $func_name: ident,
+        $func_name_with: ident,
+        $header_parser: ident,
+        $section_parser: ident,
+        $segment_parser: ident,
+        $symbol_parser: ident,
+        $section: ident,
+        $segment: ident,
+        $symbol: ident,
+        $raw_symbol: ident,
+        $raw_header: ident,
+        $result: ident
+    } => {
+            pub fn $func_name(input: &[u8]) -> Result<Executable, RustepError> {
+                $func_name_with(input, &ParseOptions::default())
+            }
+
+            pub fn $func_name_with<'a>(
+                input: &'a [u8],
+                options: &ParseOptions
+            ) -> Result<Executable<'a>, RustepError> {
+                if input.len() < mem::size_of::<$raw_header>() {
+                    Err(RustepError::FileTooSmall {
+                        needed: mem::size_of::<$raw_header>(),
+                        got: input.len(),
+                    })?
+                }
+                let hdr = nom_try!($header_parser(input), 0, "ELF header");
+                // Extended numbering: counts which do not fit in the header are kept in the
+                // first section header, `sh_size` for the sections and `sh_info` for the segments
+                let first_section = if hdr.e_shoff != 0
+                    && (hdr.e_shnum == 0 || hdr.e_phnum as u32 == PN_XNUM)
+                {
+                    input
+                        .get(hdr.e_shoff as usize..)
+                        .and_then(|table| $section_parser(table).to_result().ok())
+                } else {
+                    None
+                };
+                let section_count = match first_section {
+                    Some(ref s) if hdr.e_shnum == 0 => s.sh_size as u64,
+                    _ => hdr.e_shnum as u64,
+                };
+                let segment_count = match first_section {
+                    Some(ref s) if hdr.e_phnum as u32 == PN_XNUM => s.sh_info as u64,
+                    _ => hdr.e_phnum as u64,
+                };
+                if section_count > options.max_sections as u64 {
+                    Err(RustepError::TooManySections(section_count))?
+                }
+                if cmp::max(section_count, segment_count) > options.max_entries as u64 {
+                    Err(RustepError::TooManyEntries(cmp::max(section_count, segment_count)))?
+                }
+                // An empty table may come with any offset, usually 0
+                let program_headers = if segment_count == 0 {
+                    Vec::new()
+                } else {
+                    nom_try!(preceded!(
+                        input,
+                        take!(hdr.e_phoff),
+                        count!(call!($segment_parser), segment_count as usize)
+                    ), hdr.e_phoff, "program header table")
+                };
+                if options.strict && !program_headers.is_empty() {
+                    let table_start = hdr.e_phoff as u64;
+                    let table_end = table_start + segment_count * hdr.e_phentsize as u64;
+                    // Segments starting at or before the table, like the first `PT_LOAD` or
+                    // `PT_PHDR`, are the ones holding it
+                    let first_data = program_headers
+                        .iter()
+                        .filter(|p| p.p_filesz != 0 && p.p_offset as u64 > table_start)
+                        .map(|p| p.p_offset as u64)
+                        .min();
+                    if table_start < hdr.e_ehsize as u64
+                        || first_data.map_or(false, |offset| offset < table_end)
+                    {
+                        Err(RustepError::OverlappingHeaders)?
+                    }
+                }
+                // The tables are parsed already, so their length is bounded by the input
+                let mut segments = Vec::with_capacity(program_headers.len());
+                for (index, p) in program_headers.iter().enumerate() {
+                    let (data, end) = file_range(input, p.p_offset as u64, p.p_filesz as u64);
+                    if options.strict && data.len() as u64 != p.p_filesz as u64 {
+                        Err(RustepError::SegmentOutOfBounds {
+                            index: index,
+                            end: end,
+                            file_size: input.len() as u64,
+                        })?
+                    }
+                    let segment_type = MaybeKnown::from_u32(p.p_type);
+                    let flags = SegmentFlag::from_bits_retain(p.p_flags as u64);
+                    if options.strict {
+                        if let Some(reason) = segment_invariant_violation(
+                            p.p_filesz as u64,
+                            p.p_memsz as u64,
+                            p.p_align as u64
+                        ) {
+                            Err(RustepError::InvalidSegment { index: index, reason: reason })?
+                        }
+                        if let MaybeKnown::Unknown(value) = segment_type {
+                            Err(RustepError::SegmentType(value as u64))?
+                        }
+                        if SegmentFlag::from_bits(flags.bits()).is_none() {
+                            Err(RustepError::SegmentFlag(p.p_flags as u64))?
+                        }
+                    }
+                    let segment = $segment {
+                        phdr: *p,
+                        segment_type: segment_type,
+                        flags: flags,
+                        data: Cow::Borrowed(data)
+                    };
+            
+                    segments.push(segment);
+                }
+                let section_headers = if section_count == 0 {
+                    Vec::new()
+                } else {
+                    nom_try!(preceded!(
+                        input,
+                        take!(hdr.e_shoff),
+                        count!(call!($section_parser), section_count as usize)
+                    ), hdr.e_shoff, "section header table")
+                };
+                // With extended numbering, the index is in `sh_link` of the first section header
+                let shstrndx = match section_headers.first() {
+                    Some(first) if hdr.e_shstrndx as u32 == SHN_XINDEX => first.sh_link as usize,
+                    _ => hdr.e_shstrndx as usize,
+                };
+                // Names read from anything else than a string table would be garbage, index 0
+                // (`SHN_UNDEF`) means there are none
+                let strtab_data = match section_headers.get(shstrndx) {
+                    Some(s) if s.sh_type == SectionType::SHT_STRTAB as u32 => {
+                        Some(file_range(input, s.sh_offset as u64, s.sh_size as u64).0)
+                    },
+                    Some(_) if options.strict && shstrndx != 0 => {
+                        Err(RustepError::InvalidStringTable(shstrndx))?
+                    },
+                    _ => None,
+                };
+                let strtab = match (strtab_data, options.resolve_names) {
+                    (Some(data), true) => Some(IndexedStringTable::new(data)),
+                    _ => None,
+                };
+
+                let section_name = |index: usize, sh_name: u32| -> Result<String, RustepError> {
+                    let bytes = match strtab {
+                        Some(ref strtab) => strtab.get_bytes(sh_name),
+                        _ => return Ok(String::new()),
+                    };
+                    match bytes {
+                        Some(bytes) => String::from_utf8(bytes.to_vec())
+                            .map_err(|_| RustepError::InvalidSectionName(index)),
+                        None if options.strict => Err(RustepError::InvalidStringIndex(sh_name as u64)),
+                        // Leave the name empty
+                        None => Ok(String::new()),
+                    }
+                };
+
+                let mut sections = Vec::with_capacity(section_headers.len());
+                for (index, s) in section_headers.iter().enumerate() {
+                    // The filter needs the name, otherwise names are resolved once all the
+                    // sections are checked
+                    let name = match options.section_filter {
+                        Some(filter) => {
+                            let name = section_name(index, s.sh_name)?;
+                            if !filter(&name, s) {
+                                continue;
+                            }
+                            name
+                        },
+                        None => String::new(),
+                    };
+                    let (data, end) = file_range(input, s.sh_offset as u64, s.sh_size as u64);
+                    let section_type = MaybeKnown::from_u32(s.sh_type);
+                    // `SHT_NOBITS` sections take no room in the file, whatever their size
+                    let truncated = data.len() as u64 != s.sh_size as u64
+                        && section_type != SectionType::SHT_NOBITS;
+                    if options.strict && truncated {
+                        Err(RustepError::SectionOutOfBounds {
+                            index: index,
+                            end: end,
+                            file_size: input.len() as u64,
+                        })?
+                    }
+                    let flags = SectionFlag::from_bits_retain(s.sh_flags as u64);
+                    if options.strict {
+                        if let MaybeKnown::Unknown(value) = section_type {
+                            Err(RustepError::SectionType(value as u64))?
+                        }
+                        if SectionFlag::from_bits(flags.bits()).is_none() {
+                            Err(RustepError::SectionFlag(s.sh_flags as u64))?
+                        }
+                    }
+
+                    let section = $section {
+                        name: name,
+                        index: index,
+                        shdr: *s,
+                        section_type: section_type,
+                        flags: flags,
+                        data: Cow::Borrowed(data)
+                    };
+            
+                    sections.push(section);
+                }
+                if options.section_filter.is_none() {
+                    for s in sections.iter_mut() {
+                        s.name = section_name(s.index, s.shdr.sh_name)?;
+                    }
+                }
+
+            let mut symbols = Vec::new();
+            let mut dynamic_symbols = Vec::new();
+            for s in sections.iter().filter(|_| options.parse_symbols) {
+                let table = match s.section_type {
+                    MaybeKnown::Known(SectionType::SHT_SYMTAB) => &mut symbols,
+                    MaybeKnown::Known(SectionType::SHT_DYNSYM) => &mut dynamic_symbols,
+                    _ => continue,
+                };
+                // Names borrow from the input, section data is not copied at this point. The
+                // string table is read from its header, it may have been filtered out
+                let strtab = IndexedStringTable::new(
+                    match section_headers.get(s.shdr.sh_link as usize) {
+                        Some(link) if options.resolve_names => {
+                            file_range(input, link.sh_offset as u64, link.sh_size as u64).0
+                        },
+                        _ => &[],
+                    }
+                );
+                let entry_count = s.data.len() / mem::size_of::<$raw_symbol>();
+                let entries = nom_try!(
+                    count!(&s.data[..], $symbol_parser, entry_count),
+                    s.shdr.sh_offset,
+                    "symbol table"
+                );
+                table.reserve(entries.len());
+                for sym in entries {
+                    let name = if options.resolve_names {
+                        strtab
+                            .get_bytes(sym.st_name)
+                            .map(String::from_utf8_lossy)
+                            .unwrap_or_default()
+                    } else {
+                        Cow::Borrowed("")
+                    };
+                    table.push($symbol {
+                        name: name,
+                        sym: sym,
+                    });
+                }
+            }
+        
+            let phdrs_end = hdr.e_phoff as u64 + segment_count * hdr.e_phentsize as u64;
+            let shdrs_end = hdr.e_shoff as u64 + section_count * hdr.e_shentsize as u64;
+            let structures_end = sections
+                .iter()
+                .filter(|s| s.section_type != SectionType::SHT_NOBITS)
+                .map(|s| (s.shdr.sh_offset as u64).saturating_add(s.shdr.sh_size as u64))
+                .chain(segments
+                    .iter()
+                    .map(|p| (p.phdr.p_offset as u64).saturating_add(p.phdr.p_filesz as u64)))
+                .chain(vec![hdr.e_ehsize as u64, phdrs_end, shdrs_end])
+                .max()
+                .unwrap_or(0);
+            let struct_ins = $result {
+                header: hdr,
+                elf_type: FromPrimitive::from_u16(hdr.e_type)
+                    .ok_or(RustepError::ElfType(hdr.e_type as u64))?,
+                sections: sections,
+                segments: segments,
+                symbols: symbols,
+                dynamic_symbols: dynamic_symbols,
+                file_size: input.len() as u64,
+                section_count: section_count,
+                raw_program_headers: Cow::Borrowed(file_range(
+                    input,
+                    hdr.e_phoff as u64,
+                    segment_count * hdr.e_phentsize as u64
+                ).0),
+                raw_section_headers: Cow::Borrowed(file_range(
+                    input,
+                    hdr.e_shoff as u64,
+                    section_count * hdr.e_shentsize as u64
+                ).0),
+                overlay: Cow::Borrowed(&input[cmp::min(structures_end, input.len() as u64) as usize..]),
+            };
+            Ok(Executable::$result(struct_ins))
+        }
+    }
+}
+
+// TODO Refactor this when `Rust`'s contant generic is done.
+// I really don't want to write duplicate code, macro is my final option to avoid that.
+define_elf_parser!{
+    parse_elf32,
+    parse_elf32_with,
+    parse_elf_header32,
+    parse_elf_section_header32,
+    parse_elf_prog_header32,
+    parse_elf_symbol32,
+    ElfSection32,
+    ElfSegment32,
+    ElfSymbol32,
+    Elf32_Sym,
+    Elf32_Ehdr,
+    Elf32
+}
+define_elf_parser!{
+    parse_elf64,
+    parse_elf64_with,
+    parse_elf_header64,
+    parse_elf_section_header64,
+    parse_elf_prog_header64,
+    parse_elf_symbol64,
+    ElfSection64,
+    ElfSegment64,
+    ElfSymbol64,
+    Elf64_Sym,
+    Elf64_Ehdr,
+    Elf64
+}
+
+#[test]
+fn test_parse_elf32() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    
+    let result = parse_elf32(&buf).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.header().elf_type().unwrap(), ElfType::ET_DYN);
+    }
+    match result {
+        Executable::Elf32(res) => {
+            let section = res.sections[1].shdr;
+            assert_eq!(section.sh_name, 0x1b);
+            assert_eq!(section.sh_type, 1);
+            assert_eq!(section.sh_flags, 2);
+            assert_eq!(section.sh_addr, 0x154);
+            assert_eq!(section.sh_offset, 0x154);
+            assert_eq!(section.sh_size, 19);
+            assert_eq!(section.sh_link, 0);
+            assert_eq!(section.sh_info, 0);
+            assert_eq!(section.sh_addralign, 1);
+            assert_eq!(section.sh_entsize, 0);
+
+            let segment = res.segments[0].phdr;
+            assert_eq!(segment.p_type, 6);
+            assert_eq!(segment.p_offset, 0x34);
+            assert_eq!(segment.p_vaddr, 0x34);
+            assert_eq!(segment.p_paddr, 0x34);
+            assert_eq!(segment.p_filesz, 288);
+            assert_eq!(segment.p_memsz, 288);
+            assert_eq!(segment.p_flags, 5);
+            assert_eq!(segment.p_align, 4);
+        },
+        _ => panic!("Wrong file format detection"),
+    };
+
+}
+
+#[test]
+fn test_parse_elf() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    
+    let result = parse_elf(&buf).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.header().elf_type().unwrap(), ElfType::ET_DYN);
+    }
+    match result {
+        Executable::Elf64(res) => {
+            let section = res.sections[1].shdr;
+            assert_eq!(section.sh_name, 0x1b);
+            assert_eq!(section.sh_type, 1);
+            assert_eq!(section.sh_flags, 2);
+            assert_eq!(section.sh_addr, 0x238);
+            assert_eq!(section.sh_offset, 0x238);
+            assert_eq!(section.sh_size, 28);
+            assert_eq!(section.sh_link, 0);
+            assert_eq!(section.sh_info, 0);
+            assert_eq!(section.sh_addralign, 1);
+            assert_eq!(section.sh_entsize, 0);
+
+            let segment = res.segments[0].phdr;
+            assert_eq!(segment.p_type, 6);
+            assert_eq!(segment.p_offset, 0x40);
+            assert_eq!(segment.p_vaddr, 0x40);
+            assert_eq!(segment.p_paddr, 0x40);
+            assert_eq!(segment.p_filesz, 504);
+            assert_eq!(segment.p_memsz, 504);
+            assert_eq!(segment.p_flags, 5);
+            assert_eq!(segment.p_align, 8);
+
+            assert_eq!(res.elf_type, ElfType::ET_DYN);
+        },
+        _ => panic!("Wrong file format detection"),
+    };
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    
+    let result = parse_elf(&buf).unwrap();
+    match result {
+        Executable::Elf32(res) => {
+            let section = res.sections[1].shdr;
+            assert_eq!(section.sh_name, 0x1b);
+            assert_eq!(section.sh_type, 1);
+            assert_eq!(section.sh_flags, 2);
+            assert_eq!(section.sh_addr, 0x154);
+            assert_eq!(section.sh_offset, 0x154);
+            assert_eq!(section.sh_size, 19);
+            assert_eq!(section.sh_link, 0);
+            assert_eq!(section.sh_info, 0);
+            assert_eq!(section.sh_addralign, 1);
+            assert_eq!(section.sh_entsize, 0);
+
+            let segment = res.segments[0].phdr;
+            assert_eq!(segment.p_type, 6);
+            assert_eq!(segment.p_offset, 0x34);
+            assert_eq!(segment.p_vaddr, 0x34);
+            assert_eq!(segment.p_paddr, 0x34);
+            assert_eq!(segment.p_filesz, 288);
+            assert_eq!(segment.p_memsz, 288);
+            assert_eq!(segment.p_flags, 5);
+            assert_eq!(segment.p_align, 4);
+
+            assert_eq!(res.elf_type, ElfType::ET_DYN);
+        },
+        _ => panic!("Wrong file format detection"),
+    };
+}
+
+#[test]
+fn test_parse_elf64() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    
+    let result = parse_elf64(&buf).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.header().elf_type().unwrap(), ElfType::ET_DYN);
+    }
+    match result {
+        Executable::Elf64(res) => {
+            let section = res.sections[1].shdr;
+            assert_eq!(section.sh_name, 0x1b);
+            assert_eq!(section.sh_type, 1);
+            assert_eq!(section.sh_flags, 2);
+            assert_eq!(section.sh_addr, 0x238);
+            assert_eq!(section.sh_offset, 0x238);
+            assert_eq!(section.sh_size, 28);
+            assert_eq!(section.sh_link, 0);
+            assert_eq!(section.sh_info, 0);
+            assert_eq!(section.sh_addralign, 1);
+            assert_eq!(section.sh_entsize, 0);
+
+            let segment = res.segments[0].phdr;
+            assert_eq!(segment.p_type, 6);
+            assert_eq!(segment.p_offset, 0x40);
+            assert_eq!(segment.p_vaddr, 0x40);
+            assert_eq!(segment.p_paddr, 0x40);
+            assert_eq!(segment.p_filesz, 504);
+            assert_eq!(segment.p_memsz, 504);
+            assert_eq!(segment.p_flags, 5);
+            assert_eq!(segment.p_align, 8);
+        },
+        _ => panic!("Wrong file format detection"),
+    }
+
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_to_json() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let json = res.to_json();
+    assert!(json.starts_with("{\"header\":{\"type\":\"ET_DYN\",\"machine\":\"X86_64\""));
+    assert!(json.contains("{\"name\":\".text\",\"type\":\"SHT_PROGBITS\",\"flags\":[\"SHF_ALLOC\",\"SHF_EXECINSTR\"]"));
+    assert!(json.contains("{\"type\":\"PT_LOAD\",\"flags\":[\"PF_X\",\"PF_R\"]"));
+}
+
+#[test]
+fn test_display() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(
+        format!("{}", res.header()),
+        "ELF Header:
+  Type:                              ET_DYN
+  Machine:                           X86_64
+  Entry point address:               0x540
+  Start of program headers:          64 (bytes into file)
+  Start of section headers:          7744 (bytes into file)
+  Size of this header:               64 (bytes)
+  Size of program headers:           56 (bytes)
+  Number of program headers:         9
+  Size of section headers:           64 (bytes)
+  Number of section headers:         30
+  Section header string table index: 29"
+    );
+    assert_eq!(
+        format!("{}", res.section(".text").unwrap()),
+        ".text SHT_PROGBITS [AX] address: 0x540 size: 0x1b2"
+    );
+    assert_eq!(
+        format!("{}", res.segments()[2]),
+        "PT_LOAD [R E] address: 0x0 file size: 0x830 memory size: 0x830"
+    );
+    assert_eq!(section_flags_short(SectionFlag::SHF_WRITE | SectionFlag::SHF_ALLOC), "WA");
+}
+
+#[test]
+fn test_format_section_table() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked against `readelf -SW test/test32`
+    assert_eq!(res.format_section_table(), "  [Nr] Name               Type            Address  Off      Size     ES Flg Lk Inf Al
+  [ 0]                    NULL            00000000 00000000 00000000 00      0   0  0
+  [ 1] .interp            PROGBITS        00000154 00000154 00000013 00   A  0   0  1
+  [ 2] .note.ABI-tag      NOTE            00000168 00000168 00000020 00   A  0   0  4
+  [ 3] .note.gnu.build-id NOTE            00000188 00000188 00000024 00   A  0   0  4
+  [ 4] .gnu.hash          GNU_HASH        000001ac 000001ac 00000020 04   A  5   0  4
+  [ 5] .dynsym            DYNSYM          000001cc 000001cc 00000080 10   A  6   1  4
+  [ 6] .dynstr            STRTAB          0000024c 0000024c 0000009d 00   A  0   0  1
+  [ 7] .gnu.version       GNU_versym      000002ea 000002ea 00000010 02   A  5   0  2
+  [ 8] .gnu.version_r     GNU_verneed     000002fc 000002fc 00000030 00   A  6   1  4
+  [ 9] .rel.dyn           REL             0000032c 0000032c 00000040 08   A  5   0  4
+  [10] .rel.plt           REL             0000036c 0000036c 00000010 08  AI  5  23  4
+  [11] .init              PROGBITS        0000037c 0000037c 00000023 00  AX  0   0  4
+  [12] .plt               PROGBITS        000003a0 000003a0 00000030 04  AX  0   0 16
+  [13] .plt.got           PROGBITS        000003d0 000003d0 00000008 08  AX  0   0  8
+  [14] .text              PROGBITS        000003e0 000003e0 000001f2 00  AX  0   0 16
+  [15] .fini              PROGBITS        000005d4 000005d4 00000014 00  AX  0   0  4
+  [16] .rodata            PROGBITS        000005e8 000005e8 0000000c 00   A  0   0  4
+  [17] .eh_frame_hdr      PROGBITS        000005f4 000005f4 0000003c 00   A  0   0  4
+  [18] .eh_frame          PROGBITS        00000630 00000630 000000fc 00   A  0   0  4
+  [19] .init_array        INIT_ARRAY      00001ef4 00000ef4 00000004 04  WA  0   0  4
+  [20] .fini_array        FINI_ARRAY      00001ef8 00000ef8 00000004 04  WA  0   0  4
+  [21] .dynamic           DYNAMIC         00001efc 00000efc 000000f0 08  WA  6   0  4
+  [22] .got               PROGBITS        00001fec 00000fec 00000014 04  WA  0   0  4
+  [23] .got.plt           PROGBITS        00002000 00001000 00000014 04  WA  0   0  4
+  [24] .data              PROGBITS        00002014 00001014 00000008 00  WA  0   0  4
+  [25] .bss               NOBITS          0000201c 0000101c 00000004 00  WA  0   0  1
+  [26] .comment           PROGBITS        00000000 0000101c 0000002b 01  MS  0   0  1
+  [27] .GCC.command.line  PROGBITS        00000000 00001047 000004bd 01  MS  0   0  1
+  [28] .symtab            SYMTAB          00000000 00001504 00000460 10     29  46  4
+  [29] .strtab            STRTAB          00000000 00001964 00000250 00      0   0  1
+  [30] .shstrtab          STRTAB          00000000 00001bb4 00000117 00      0   0  1
+");
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let table = res.format_section_table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 31);
+    assert_eq!(
+        lines[14],
+        "  [13] .text              PROGBITS        0000000000000540 00000540 000001b2 00  AX  0   0 16"
+    );
+}
+
+#[test]
+fn test_is_stripped() {
+    use std::{fs::File, io::prelude::*};
+
+    for &(path, stripped, debug) in [
+        ("test/test", false, false),
+        ("test/test_stripped", true, false),
+        ("test/test_debug.o", false, true),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.is_stripped(), stripped, "{}", path);
+        assert_eq!(res.has_debug_info(), debug, "{}", path);
+    }
+}
+
+#[test]
+fn test_machine() {
+    use std::{fs::File, io::prelude::*};
+
+    // (path, machine, natural pointer size of the machine, pointer size of the file)
+    for &(path, machine, machine_pointer, pointer) in [
+        ("test/test", ElfMachine::X86_64, Some(8), 8),
+        ("test/test32", ElfMachine::I386, Some(4), 4),
+        ("test/test_aarch64.o", ElfMachine::AARCH64, Some(8), 8),
+        ("test/test_arm.o", ElfMachine::ARM, Some(4), 4),
+        // RISC-V has both widths, the class tells them apart
+        ("test/test_riscv64.o", ElfMachine::RISCV, None, 8),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.header().machine().unwrap(), machine, "{}", path);
+        assert_eq!(machine.pointer_size(), machine_pointer, "{}", path);
+        assert_eq!(res.pointer_size(), pointer, "{}", path);
+    }
+    assert_eq!(FromPrimitive::from_u16(247), Some(ElfMachine::BPF));
+    assert_eq!(FromPrimitive::from_u16(258), Some(ElfMachine::LOONGARCH));
+}
+
+#[test]
+fn test_unknown_section_type() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test_arm.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // `.ARM.exidx` is `SHT_ARM_EXIDX`, which is processor specific
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let exidx = res.section(".ARM.exidx").unwrap();
+    assert_eq!(*exidx.section_type(), MaybeKnown::Unknown(0x70000001));
+    assert_eq!(exidx.section_type().known(), None);
+    assert_eq!(exidx.section_type().to_string(), "SHT_LOPROC+0x1");
+    assert_eq!(*res.section(".text").unwrap().section_type(), SectionType::SHT_PROGBITS);
+}
+
+#[test]
+fn test_type_display() {
+    assert_eq!(format!("{}", SegmentType::PT_LOAD), "PT_LOAD");
+    assert_eq!(format!("{}", SectionType::SHT_PROGBITS), "SHT_PROGBITS");
+    assert_eq!(MaybeKnown::<SegmentType>::from_u32(1).to_string(), "PT_LOAD");
+    assert_eq!(MaybeKnown::<SegmentType>::from_u32(0x6474e553).to_string(), "PT_LOOS+0x474e553");
+    assert_eq!(MaybeKnown::<SegmentType>::from_u32(0x70000003).to_string(), "PT_LOPROC+0x3");
+    assert_eq!(MaybeKnown::<SegmentType>::from_u32(0x20).to_string(), "0x20");
+    assert_eq!(MaybeKnown::<SectionType>::from_u32(0x80000001).to_string(), "SHT_LOUSER+0x1");
+    assert_eq!(MaybeKnown::<DynamicTag>::from_u32(0x70000001).to_string(), "DT_LOPROC+0x1");
+}
+
+#[test]
+fn test_section_segment_eq() {
+    use std::collections::HashSet;
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let copy = buf.clone();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let other_result = parse_elf(&copy).unwrap();
+    let other: &ElfFormat = (&other_result).try_into().expect("unable to convert");
+    // Same contents at different addresses
+    let text = res.section(".text").unwrap();
+    let other_text = other.section(".text").unwrap();
+    assert!(text.data().as_ptr() != other_text.data().as_ptr());
+    assert!(text == other_text);
+    assert!(text != res.section(".fini").unwrap());
+    assert!(res.segments()[2] == other.segments()[2]);
+    assert!(res.segments()[2] != res.segments()[3]);
+
+    let sections: HashSet<&ElfSection> = res.sections()
+        .into_iter()
+        .chain(other.sections())
+        .collect();
+    assert_eq!(sections.len(), res.sections().len());
+
+    let concrete = (&result, &other_result);
+    if let (&Executable::Elf64(ref elf), &Executable::Elf64(ref other_elf)) = concrete {
+        assert!(elf.sections[13] == other_elf.sections[13]);
+        assert!(elf.segments[2] != other_elf.segments[3]);
+    } else {
+        panic!("test/test is a 64-bit file");
+    }
+}
+
+#[test]
+fn test_unknown_flag_bits() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // `SHF_EXCLUDE` on `.text`, section header 13 starting from 0x1e40, `sh_flags` at +8
+    buf[0x1e40 + 13 * 64 + 8 + 3] |= 0x80;
+    // An undefined bit on the first program header, `p_flags` at +4
+    buf[0x40 + 4] |= 0x08;
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let text = res.section(".text").unwrap();
+    assert_eq!(text.raw_flags(), 0x80000006);
+    // The unknown bits are kept, but only the known ones are named
+    assert_eq!(text.flags().bits(), 0x80000006);
+    assert!(text.flags().contains(SectionFlag::SHF_ALLOC | SectionFlag::SHF_EXECINSTR));
+    assert_eq!(
+        text.flags().iter_names().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["SHF_ALLOC", "SHF_EXECINSTR"]
+    );
+    assert_eq!(
+        text.flags().iter().collect::<Vec<_>>(),
+        vec![
+            SectionFlag::SHF_ALLOC,
+            SectionFlag::SHF_EXECINSTR,
+            SectionFlag::from_bits_retain(0x80000000),
+        ]
+    );
+    assert_eq!(section_flags_short(text.flags()), "AX");
+    let phdr = res.segments()[0];
+    assert_eq!(phdr.raw_flags(), 0xd);
+    assert_eq!(phdr.flags().bits(), 0xd);
+    assert_eq!(
+        phdr.flags().iter_names().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["PF_X", "PF_R"]
+    );
+    assert_eq!(phdr.flags() - SegmentFlag::all(), SegmentFlag::from_bits_retain(0x8));
+
+    // Strict mode still rejects them
+    let options = ParseOptions { strict: true, ..ParseOptions::default() };
+    match parse_elf_with(&buf, &options) {
+        Err(RustepError::SegmentFlag(0xd)) => (),
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_overlapping_headers() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+    // The table ends at 0x238, right where the `PT_INTERP` data starts
+    assert!(parse_elf_with(&buf, &strict).is_ok());
+
+    // Move the `PT_INTERP` data, `p_offset` of the second program header, into the table
+    let mut overlapping = buf.clone();
+    overlapping[0x40 + 56 + 8..0x40 + 56 + 16].copy_from_slice(&0x200u64.to_le_bytes());
+    match parse_elf_with(&overlapping, &strict) {
+        Err(RustepError::OverlappingHeaders) => (),
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+    let result = parse_elf(&overlapping).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.segments()[1].phdr().offset(), 0x200);
+
+    // Grow `e_ehsize` past `e_phoff`
+    let mut overlapping = buf.clone();
+    overlapping[52] = 0x48;
+    match parse_elf_with(&overlapping, &strict) {
+        Err(RustepError::OverlappingHeaders) => (),
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+    assert!(parse_elf(&overlapping).is_ok());
+}
+
+#[test]
+fn test_header_raw_fields() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for &(path, class) in [("test/test", 2u8), ("test/test32", 1u8)].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let hdr = res.header();
+        assert_eq!(hdr.version(), 1);
+        // x86 defines no `e_flags`
+        assert_eq!(hdr.flags(), 0);
+        assert_eq!(&hdr.ident()[..4], b"\x7fELF");
+        assert_eq!(hdr.ident()[4], class);
+        assert_eq!(&hdr.ident()[..], &buf[..16]);
+    }
+}
+
+#[test]
+fn test_header_as_any() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let raw = res.header().as_any().downcast_ref::<Elf64_Ehdr>().unwrap();
+    assert_eq!(raw.e_flags, 0);
+    assert_eq!(raw.e_shnum, 30);
+    assert!(res.header().as_any().downcast_ref::<Elf32_Ehdr>().is_none());
+
+    // Checked with `readelf -h test/test_arm.o`
+    let mut file = File::open("test/test_arm.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let raw = res.header().as_any().downcast_ref::<Elf32_Ehdr>().unwrap();
+    assert_eq!(raw.e_flags, 0x05000000);
+    assert_eq!(raw.e_flags, res.header().flags());
+}
+
+#[test]
+fn test_validate() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.validate(), Ok(()));
+    }
+
+    // e_shentsize is at 0x3a in the 64-bit header
+    buf[0x3a] = 0x20;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.validate(), Err(vec![
+        ValidationWarning::ShentsizeMismatch { expected: 64, found: 0x20 },
+    ]));
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // e_shentsize is at 0x2e and e_shstrndx at 0x32 in the 32-bit header
+    buf[0x2e] = 0x20;
+    buf[0x32] = 0xff;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let warnings = res.validate().unwrap_err();
+    assert_eq!(warnings[0], ValidationWarning::ShentsizeMismatch { expected: 40, found: 0x20 });
+    assert_eq!(warnings[1], ValidationWarning::ShstrndxOutOfRange { index: 0xff, count: 31 });
+    assert_eq!(warnings[1].to_string(), "e_shstrndx 255 is out of range of 31 sections");
+}
+
+#[test]
+fn test_invalid_segment() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+
+    // The second `PT_LOAD` is program header 3, at 0xe8: p_filesz is 0x250, p_memsz (0x110)
+    // set below it
+    let mut shrunk = buf.clone();
+    shrunk[0x110..0x118].copy_from_slice(&0x100u64.to_le_bytes());
+    assert_eq!(parse_elf_with(&shrunk, &strict).err(), Some(RustepError::InvalidSegment {
+        index: 3,
+        reason: "p_filesz is larger than p_memsz",
+    }));
+    let result = parse_elf(&shrunk).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.segments()[3].data().len(), 0x250);
+    assert_eq!(res.validate(), Err(vec![ValidationWarning::InvalidSegment {
+        index: 3,
+        reason: "p_filesz is larger than p_memsz",
+    }]));
+
+    // p_align (0x118) set to 0x3000
+    let mut misaligned = buf.clone();
+    misaligned[0x118..0x120].copy_from_slice(&0x3000u64.to_le_bytes());
+    match parse_elf_with(&misaligned, &strict) {
+        Err(e) => assert_eq!(e.to_string(), "Segment 3 is invalid, p_align is not a power of two"),
+        Ok(_) => panic!("alignment of 0x3000 accepted in strict mode"),
+    }
+    assert!(parse_elf(&misaligned).is_ok());
+
+    // No alignment at all is fine
+    let mut unaligned = buf.clone();
+    unaligned[0x118..0x120].copy_from_slice(&0u64.to_le_bytes());
+    assert!(parse_elf_with(&unaligned, &strict).is_ok());
+}
+
+#[test]
+fn test_by_address() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for path in ["test/test", "test/test32", "test/test_tls"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let sections = res.sections_by_address();
+        assert!(sections.iter().all(|sec| sec.is_alloc()), "{}", path);
+        assert!(sections
+            .windows(2)
+            .all(|pair| pair[0].shdr().address() <= pair[1].shdr().address()), "{}", path);
+        let alloc_count = res.sections().iter().filter(|sec| sec.is_alloc()).count();
+        assert_eq!(sections.len(), alloc_count, "{}", path);
+        // Not necessarily the same section: `.tbss` takes no room in the memory image and shares
+        // its address with the next one
+        for sec in sections.iter().filter(|sec| sec.shdr().size() != 0) {
+            assert!(res.section_at_address(sec.shdr().address()).is_some(), "{}", path);
+        }
+
+        let segments = res.segments_by_address();
+        assert!(segments.iter().all(|seg| *seg.segment_type() == SegmentType::PT_LOAD));
+        assert!(segments.windows(2).all(|pair| pair[0].phdr().vaddr() < pair[1].phdr().vaddr()));
+    }
+
+    // Checked with `readelf -S test/test`
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let names: Vec<&str> = res.sections_by_address().iter().map(|sec| sec.name()).collect();
+    assert_eq!(names.first(), Some(&".interp"));
+    assert_eq!(names.last(), Some(&".bss"));
+    assert!(!names.contains(&".symtab") && !names.contains(&".comment"));
+    let vaddrs: Vec<u64> = res.segments_by_address().iter().map(|seg| seg.phdr().vaddr()).collect();
+    assert_eq!(vaddrs, vec![0, 0x200de0]);
+}
+
+#[test]
+fn test_symbol_bytes() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // `main` is 47 bytes at 0x64a, loaded at its file offset: `push %rbp; mov %rsp,%rbp`
+    let main = res.symbol_bytes("main").unwrap();
+    assert_eq!(main, &buf[0x64a..0x64a + 47]);
+    assert_eq!(&main[..4], b"\x55\x48\x89\xe5");
+    // In `.bss`, empty, imported and missing
+    assert_eq!(res.symbol_bytes("completed.6983"), None);
+    assert_eq!(res.symbol_bytes("_edata"), None);
+    assert_eq!(res.symbol_bytes("printf@@GLIBC_2.2.5"), None);
+    assert_eq!(res.symbol_bytes("nothing"), None);
+
+    // Sections have no address yet in relocatable objects, the value is an offset in the section
+    let mut file = File::open("test/test_ifunc.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let text = res.section(".text").unwrap().data();
+    assert_eq!(res.symbol_bytes("resolve_answer"), Some(&text[6..14]));
+
+    // Stripped, `.dynsym` is used
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.symbol_bytes("answer").map(|code| code.len()), Some(6));
+}
+
+#[test]
+fn test_segment_mapping() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked against `readelf -lW test/test`
+    assert_eq!(res.format_segment_mapping(), " Section to Segment mapping:
+  Segment Sections...
+   00     
+   01     .interp
+   02     .interp .note.ABI-tag .note.gnu.build-id .gnu.hash .dynsym .dynstr .gnu.version .gnu.version_r .rela.dyn .rela.plt .init .plt .text .fini .rodata .eh_frame_hdr .eh_frame
+   03     .init_array .fini_array .dynamic .got .got.plt .data .bss
+   04     .dynamic
+   05     .note.ABI-tag .note.gnu.build-id
+   06     .eh_frame_hdr
+   07     
+   08     .init_array .fini_array .dynamic .got
+");
+    let data_segment = res.segments()[3];
+    let names: Vec<&str> = res.sections_in_segment(data_segment).iter().map(|s| s.name()).collect();
+    assert_eq!(names, vec![".init_array", ".fini_array", ".dynamic", ".got", ".got.plt", ".data", ".bss"]);
+
+    // A section too large for any segment, whose end does not fit in 64 bits
+    let interp = res.header().shoff() as usize + res.header().shentsize() as usize;
+    let mut patched = buf.clone();
+    patched[interp + 32..interp + 40].copy_from_slice(&u64::max_value().to_le_bytes());
+    let result = parse_elf(&patched).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.section_by_index(1).map(|sec| sec.name()), Some(".interp"));
+    assert!(res.format_segment_mapping().contains("   01     \n   02     .note.ABI-tag "));
+}
+
+#[test]
+fn test_flag_predicates() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    for seg in res.segments() {
+        assert_eq!(seg.is_executable(), seg.raw_flags() & 1 != 0);
+        assert_eq!(seg.is_writable(), seg.raw_flags() & 2 != 0);
+        assert_eq!(seg.is_readable(), seg.raw_flags() & 4 != 0);
+    }
+    for sec in res.sections() {
+        assert_eq!(sec.is_writable(), sec.raw_flags() & 1 != 0);
+        assert_eq!(sec.is_alloc(), sec.raw_flags() & 2 != 0);
+        assert_eq!(sec.is_executable(), sec.raw_flags() & 4 != 0);
+        assert_eq!(sec.is_tls(), sec.raw_flags() & 0x400 != 0);
+    }
+    let text = res.section(".text").unwrap();
+    assert!(text.is_alloc() && text.is_executable() && !text.is_writable());
+    let data = res.section(".data").unwrap();
+    assert!(data.is_alloc() && data.is_writable() && !data.is_executable());
+    assert!(!res.section(".comment").unwrap().is_alloc());
+}
+
+#[test]
+fn test_tls() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test_tls").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // `__thread int counter = 5; __thread char buf[64];`, checked with `readelf -l`
+    assert_eq!(res.tls(), Some(TlsInfo {
+        image: &[5, 0, 0, 0],
+        mem_size: 0x50,
+        align: 0x10,
+    }));
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.tls(), None);
+}
+
+#[test]
+fn test_entry_bytes() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // The entry 0x540 is in the first `PT_LOAD`, mapped at offset 0
+    assert_eq!(res.entry_bytes(16), Some(&buf[0x540..0x550]));
+    // Cut at the end of the segment, which ends at 0x830
+    assert_eq!(res.entry_bytes(0x1000), Some(&buf[0x540..0x830]));
+
+    // Truncated files, without the section header table which is at the end: the segment still
+    // claims the entry point, but its contents stop at the end of the file
+    for &(size, expected) in [(0x530, None), (0x548, Some(&buf[0x540..0x548]))].iter() {
+        let mut truncated = buf[..size].to_vec();
+        truncated[0x28..0x30].copy_from_slice(&0u64.to_le_bytes());
+        truncated[60..62].copy_from_slice(&0u16.to_le_bytes());
+        let result = parse_elf(&truncated).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.entry_bytes(16), expected, "{:#x}", size);
+    }
+
+    let mut file = File::open("test/test_tls").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Here the code is in the second `PT_LOAD`, at offset 0x1000 and address 0x1000
+    let entry = res.header().entry() as usize;
+    assert_eq!(res.entry_bytes(4), Some(&buf[entry..entry + 4]));
+
+    // A relocatable object has no segments at all
+    let mut file = File::open("test/test_debug.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.entry_bytes(4), None);
+}
+
+#[test]
+fn test_read_virtual() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        // Checked with `readelf -S test/test`: `.data` is at 0x201020 (offset 0x1020), `.bss`
+        // follows it at 0x201030 in the tail of the second `PT_LOAD`
+        assert_eq!(res.read_virtual(0x201020, 16), Some(buf[0x1020..0x1030].to_vec()));
+        assert_eq!(res.read_virtual(0x201030, 8), Some(vec![0; 8]));
+        assert_eq!(res.read_virtual(0x201028, 16), Some(
+            buf[0x1028..0x1030].iter().cloned().chain(vec![0; 8]).collect()
+        ));
+        // Past the end of the segment, and between the segments
+        assert_eq!(res.read_virtual(0x201030, 9), None);
+        assert_eq!(res.read_virtual(0x100000, 1), None);
+    }
+
+    // Without program headers (`e_phnum` cleared), the sections are used
+    buf[56] = 0;
+    buf[57] = 0;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.segments().is_empty());
+    assert_eq!(res.read_virtual(0x201020, 16), Some(buf[0x1020..0x1030].to_vec()));
+    assert_eq!(res.read_virtual(0x201030, 8), Some(vec![0; 8]));
+    // The sections are read one at a time
+    assert_eq!(res.read_virtual(0x201028, 16), None);
+}
+
+#[test]
+fn test_find_bytes() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // The format string of `printf` in `.rodata`, checked with `readelf -p .rodata test/test`
+    assert_eq!(res.find_string("%d\n"), vec![0x704]);
+    // The note owners
+    assert_eq!(res.find_bytes(b"GNU\0"), vec![0x260, 0x280]);
+    // The addend of the first relocation in `.rela.dyn` and the `.init_array` slot it fills,
+    // one in each `PT_LOAD`. Another copy in `.symtab` is not loaded.
+    assert_eq!(res.find_bytes(&[0x40, 0x06, 0, 0, 0, 0, 0, 0]), vec![0x428, 0x200de0]);
+    assert!(res.find_string("no such string").is_empty());
+    assert!(res.find_bytes(&[]).is_empty());
+}
+
+#[test]
+fn test_executable_segments() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -l test/test`, only the first `PT_LOAD` is executable
+    let segments = res.executable_segments();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].0, 0);
+    assert_eq!(segments[0].1, &buf[..0x830]);
+    let text = res.section(".text").unwrap().shdr();
+    assert!(text.address() + text.size() <= segments[0].1.len() as u64);
+
+    // `.init`, `.plt`, `.text` and `.fini`
+    let sections = res.executable_sections();
+    assert_eq!(sections.len(), 4);
+    assert_eq!(sections[2], (text.address(), res.section(".text").unwrap().data()));
+}
+
+#[test]
+fn test_code_and_data_size() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -S test/test`: `.init`, `.plt`, `.text` and `.fini`, then
+    // `.init_array` to `.data`, and the 8 bytes of `.bss`
+    assert_eq!(res.code_size(), 0x17 + 0x20 + 0x1b2 + 0x9);
+    assert_eq!(res.data_size(), 0x8 + 0x8 + 0x1e0 + 0x30 + 0x20 + 0x10 + 0x8);
+    assert_eq!(res.data_file_size(), res.data_size() - 0x8);
+
+    // The same totals by hand
+    let (mut code, mut data, mut data_file) = (0, 0, 0);
+    for sec in res.sections() {
+        let size = sec.shdr().size();
+        if sec.is_executable() {
+            code += size;
+        } else if sec.is_alloc() && sec.is_writable() {
+            data += size;
+            if *sec.section_type() != SectionType::SHT_NOBITS {
+                data_file += size;
+            }
+        }
+    }
+    assert_eq!((res.code_size(), res.data_size(), res.data_file_size()), (code, data, data_file));
+}
+
+#[test]
+fn test_load_bounds() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Checked with `readelf -l`
+    for &(path, bounds) in [
+        ("test/test", Some((0, 0x201038))),
+        ("test/test32", Some((0, 0x2020))),
+        ("test/test_nopie", Some((0x400000, 0x404020))),
+        ("test/test_common.o", None),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.load_bounds(), bounds, "{}", path);
+        assert_eq!(res.image_base(), bounds.map_or(0, |(start, _)| start), "{}", path);
+    }
+
+    // `p_memsz` of the second `PT_LOAD` (program header 3) running past the address space
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    buf[0xe8 + 40..0xe8 + 48].copy_from_slice(&u64::max_value().to_le_bytes());
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.load_bounds(), Some((0, u64::max_value())));
+}
+
+#[test]
+fn test_memory_map() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -l test/test`, `.bss` makes the second one 8 bytes larger in memory
+    assert_eq!(res.memory_map(), vec![
+        MemRegion {
+            vaddr: 0,
+            size: 0x830,
+            file_offset: 0,
+            file_size: 0x830,
+            perms: "r-xp".to_string(),
+        },
+        MemRegion {
+            vaddr: 0x200de0,
+            size: 0x258,
+            file_offset: 0xde0,
+            file_size: 0x250,
+            perms: "rw-p".to_string(),
+        },
+    ]);
+
+    let mut file = File::open("test/test_common.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.memory_map().is_empty());
+}
+
+#[test]
+fn test_section_link_info() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -S test/test`
+    let sections = res.sections();
+    let linked = |name: &str| sections[res.section(name).unwrap().shdr().link() as usize].name();
+    assert_eq!(linked(".rela.dyn"), ".dynsym");
+    assert_eq!(linked(".dynsym"), ".dynstr");
+    assert_eq!(linked(".symtab"), ".strtab");
+    let rela_plt = res.section(".rela.plt").unwrap().shdr();
+    assert_eq!(sections[rela_plt.info() as usize].name(), ".got.plt");
+    assert_eq!(res.section(".symtab").unwrap().shdr().info(), 45);
+    assert_eq!(rela_plt.addr_align(), 8);
+    assert_eq!(res.section(".plt").unwrap().shdr().addr_align(), 16);
+}
+
+#[test]
+fn test_section_index() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -S test/test`
+    let dynsym = res.section(".dynsym").unwrap();
+    assert_eq!(dynsym.index(), 5);
+    assert_eq!(res.section_index(".dynstr"), Some(dynsym.shdr().link() as usize));
+    assert_eq!(res.section_index(".shstrtab"), Some(res.header().shstrndx() as usize));
+    assert_eq!(res.section_index("no such section"), None);
+    for (i, sec) in res.sections().iter().enumerate() {
+        assert_eq!(sec.index(), i);
+    }
+
+    assert_eq!(res.section_or_err(".dynsym").map(|sec| sec.index()), Ok(5));
+    match res.section_or_err("no such section") {
+        Err(e) => {
+            assert!(e.to_string().contains("no such section"));
+            assert_eq!(e, RustepError::SectionNotFound("no such section".to_string()));
+        }
+        Ok(_) => panic!("missing section found"),
+    }
+}
+
+#[test]
+fn test_segment_alignment_ok() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for path in ["test/test", "test/test32", "test/test_nopie", "test/test_core"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.segment_alignment_ok(), "{}", path);
+    }
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // Program headers start at 0x40, move `p_vaddr` of the second `PT_LOAD` (index 3) to
+    // 0x200de8 while it is still read from offset 0xde0
+    buf[0x40 + 56 * 3 + 16] = 0xe8;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.segments()[3].phdr().align(), 0x200000);
+    assert!(!res.segment_alignment_ok());
+}
+
+#[test]
+fn test_rwx_segments() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(!res.has_rwx_segment());
+        assert!(res.writable_executable_segments().is_empty());
+    }
+
+    // Program headers start at 0x40, make the first `PT_LOAD` (index 2) writable: `p_flags`
+    // comes right after `p_type`
+    buf[0x40 + 56 * 2 + 4] |= 2;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.has_rwx_segment());
+    let rwx = res.writable_executable_segments();
+    assert_eq!(rwx.len(), 1);
+    assert_eq!(*rwx[0].segment_type(), SegmentType::PT_LOAD);
+    assert_eq!(rwx[0].phdr().file_size(), 0x830);
+}
+
+#[test]
+fn test_entry_section_and_symbol() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Checked with `readelf -h -s`
+    for &(path, section, symbol) in [
+        ("test/test", Some(".text"), Some("_start")),
+        ("test/test32", Some(".text"), Some("_start")),
+        ("test/test_nopie", Some(".text"), Some("_start")),
+        ("test/test_lib.so", None, None),
+        ("test/test_common.o", None, None),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.entry_section().map(|sec| sec.name()), section, "{}", path);
+        assert_eq!(res.entry_symbol().map(|sym| sym.name()), symbol, "{}", path);
+    }
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.section_at_address(0x704).unwrap().name(), ".rodata");
+    assert_eq!(res.section_at_address(0x201030).unwrap().name(), ".bss");
+    // Past the end of `.bss`, and a non-allocated section
+    assert!(res.section_at_address(0x201038).is_none());
+    assert!(res.section_at_address(0x10).is_none());
+}
+
+#[test]
+fn test_relocatable_locations() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test_debug.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -S -s -r test/test_debug.o`, every section is at address 0
+    assert!(res.is_relocatable());
+    assert!(res.section_at_address(0).is_none());
+    assert!(res.read_virtual(0, 4).is_none());
+    let symbols = res.symbols();
+    let main = symbols.iter().find(|sym| sym.name() == "main").unwrap();
+    let (sec, offset) = res.symbol_location(*main).unwrap();
+    assert_eq!((sec.name(), offset), (".text", 0));
+    let printf = symbols.iter().find(|sym| sym.name() == "printf").unwrap();
+    assert!(res.symbol_location(*printf).is_none());
+
+    let rela_text = res.section(".rela.text").unwrap();
+    assert_eq!(res.relocated_section(rela_text).unwrap().name(), ".text");
+    let relocs = res.relocations(rela_text).unwrap();
+    // The `R_X86_64_PLT32` against printf, the call operand is left zeroed by the compiler
+    let (sec, offset) = res.relocation_location(rela_text, &relocs[1]).unwrap();
+    assert_eq!((sec.name(), offset), (".text", 0x19));
+    assert_eq!(&sec.data()[offset as usize..offset as usize + 4], &[0, 0, 0, 0]);
+    let rela_debug_info = res.section(".rela.debug_info").unwrap();
+    let relocs = res.relocations(rela_debug_info).unwrap();
+    let (sec, offset) = res.relocation_location(rela_debug_info, &relocs[0]).unwrap();
+    assert_eq!((sec.name(), offset), (".debug_info", 8));
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(!res.is_relocatable());
+    let symbols = res.symbols();
+    let main = symbols.iter().find(|sym| sym.name() == "main").unwrap();
+    let (sec, offset) = res.symbol_location(*main).unwrap();
+    assert_eq!((sec.name(), offset), (".text", 0x64a - 0x540));
+    // The `R_X86_64_GLOB_DAT` of `__libc_start_main`, dynamic relocations have no `sh_info`
+    let rela_dyn = res.section(".rela.dyn").unwrap();
+    assert!(res.relocated_section(rela_dyn).is_none());
+    let relocs = res.relocations(rela_dyn).unwrap();
+    let (sec, offset) = res.relocation_location(rela_dyn, &relocs[5]).unwrap();
+    assert_eq!((sec.name(), offset), (".got", 8));
+}
+
+#[test]
+fn test_raw_header_tables() {
+    use std::{fs::File, io::prelude::*};
+
+    for path in &["test/test", "test/test32", "test/test_debug.o"] {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let hdr = res.header();
+        let phdrs = res.raw_program_headers();
+        assert_eq!(phdrs.len(), hdr.phentsize() as usize * hdr.phnum() as usize, "{}", path);
+        let phoff = hdr.phoff() as usize;
+        assert_eq!(phdrs, &buf[phoff..phoff + phdrs.len()], "{}", path);
+        let shdrs = res.raw_section_headers();
+        assert_eq!(shdrs.len(), hdr.shentsize() as usize * hdr.shnum() as usize, "{}", path);
+        let shoff = hdr.shoff() as usize;
+        assert_eq!(shdrs, &buf[shoff..shoff + shdrs.len()], "{}", path);
+    }
+
+    // Clearing `e_shnum` empties the table, though `e_shoff` still points to it
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    buf[60] = 0;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.raw_section_headers().is_empty());
+    assert_eq!(res.raw_program_headers().len(), 9 * 56);
+}
+
+#[test]
+fn test_uncovered_ranges() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -lS test/test`: the padding between the two `PT_LOAD` and the one
+    // aligning `.symtab`
+    assert_eq!(res.uncovered_ranges(), vec![(0x830, 0xde0), (0x1502, 0x1508)]);
+
+    let len = buf.len() as u64;
+    buf.extend_from_slice(&[0x90; 0x100]);
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(
+        res.uncovered_ranges(),
+        vec![(0x830, 0xde0), (0x1502, 0x1508), (len, len + 0x100)]
+    );
+}
+
+#[test]
+fn test_overlay() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // The section header table ends the file
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.overlay(), None);
+
+    buf.extend_from_slice(b"PAYLOAD");
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.overlay(), Some(&b"PAYLOAD"[..]));
+    if let Executable::Elf64(ref elf) = result {
+        assert_eq!(elf.to_owned().overlay(), Some(&b"PAYLOAD"[..]));
+    }
+
+    // `.bss` takes no room in the file, whatever its size
+    let mut file = File::open("test/test_ctors").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.overlay(), None);
+}
+
+#[test]
+fn test_section_data() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -SW test/test`
+    let comment = res.section_data(".comment").unwrap();
+    assert_eq!(&comment[..], &buf[0x1030..0x1030 + 0x2b]);
+    assert!(comment.starts_with(b"GCC: "));
+    assert!(res.section_data(".bss").unwrap().is_empty());
+    assert!(res.section_data(".nonexistent").is_none());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_section_data_compressed() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test_compressed.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Sizes checked with `objcopy --decompress-debug-sections`
+    let info = res.section_data(".debug_info").unwrap();
+    assert_eq!(info.len(), 0xaf);
+    let strings = res.section_data(".debug_str").unwrap();
+    assert_eq!(strings.len(), 0xc8);
+    assert!(strings.starts_with(b"unsigned int\0"));
+
+    // A `ch_size` which does not match the stream leaves the raw contents, without trying to
+    // allocate it upfront
+    let offset = res.section(".debug_info").unwrap().shdr().offset() as usize;
+    for &size in [0x7fff_ffff_ffff_0000u64, 0x10].iter() {
+        let mut patched = buf.clone();
+        patched[offset + 8..offset + 16].copy_from_slice(&size.to_le_bytes());
+        let result = parse_elf(&patched).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let info = res.section_data(".debug_info").unwrap();
+        assert_eq!(&info[..], res.section(".debug_info").unwrap().data());
+    }
+}
+
+#[test]
+fn test_debug_sections() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Built with `-g`, checked with `readelf -S`
+    let mut file = File::open("test/test_debug.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let sections = res.debug_sections();
+    assert!(!sections[".debug_info"].is_empty());
+    assert_eq!(&sections[".debug_abbrev"][..], res.section(".debug_abbrev").unwrap().data());
+    assert!(sections.keys().all(|name| name.starts_with(".debug_")));
+    // Relocations of the debug sections are not DWARF
+    assert!(!sections.contains_key(".rela.debug_info"));
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.debug_sections().is_empty());
+
+    #[cfg(feature = "compression")]
+    {
+        let mut file = File::open("test/test_compressed.o").unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let sections = res.debug_sections();
+        assert_eq!(sections[".debug_info"].len(), 0xaf);
+        assert!(sections[".debug_str"].starts_with(b"unsigned int\0"));
+    }
+}
+
+#[test]
+fn test_zdebug_sections() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Built with `-g -gz=zlib-gnu`, `.debug_str` and `.debug_line_str` are left uncompressed.
+    // Sizes checked with `objcopy --decompress-debug-sections`
+    let mut file = File::open("test/test_zdebug.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.has_debug_info());
+    let zdebug_info = res.section(".zdebug_info").unwrap();
+    assert!(zdebug_info.data().starts_with(b"ZLIB"));
+    assert!(res.section(".debug_info").is_none());
+    assert_eq!(res.section_data(".debug_str").unwrap().len(), 0x65);
+
+    let sections = res.debug_sections();
+    #[cfg(feature = "compression")]
+    {
+        for &(name, size) in [
+            (".debug_info", 0x80),
+            (".debug_abbrev", 0x66),
+            (".debug_aranges", 0x30),
+            (".debug_line", 0x58),
+            (".debug_str", 0x65),
+            (".debug_line_str", 0x16),
+        ].iter() {
+            assert_eq!(sections[name].len(), size, "{}", name);
+            assert_eq!(res.section_data(name).unwrap().len(), size, "{}", name);
+        }
+        assert_eq!(sections.len(), 6);
+        assert_eq!(res.section_data(".zdebug_info").unwrap().len(), 0x80);
+        // DWARF 5 compilation unit header, after the 4 bytes length
+        assert_eq!(&sections[".debug_info"][4..6], &[5, 0]);
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        assert_eq!(&sections[".zdebug_info"][..], zdebug_info.data());
+        assert!(res.section_data(".debug_info").is_none());
+    }
+}
+
+#[test]
+fn test_comment() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -p .comment test/test`
+    assert_eq!(res.comment(), vec!["GCC: (GNU) 7.2.1 20171224", "GCC: (GNU) 7.3.0"]);
+
+    let mut file = File::open("test/test_stripped").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.comment().iter().any(|c| c.starts_with("GCC: ")));
+
+    // Assembled with `llvm-mc`, which writes no `.comment`
+    let mut file = File::open("test/test_aarch64.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.comment().is_empty());
+}
+
+#[test]
+fn test_is_pie() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // (file, PIE, shared library)
+    let files = [
+        ("test/test", true, false),
+        ("test/test32", true, false),
+        ("test/test_lib.so", false, true),
+        ("test/test_nopie", false, false),
+        ("test/test_debug.o", false, false),
+    ];
+    for &(path, pie, shared) in files.iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.is_pie(), pie, "{}", path);
+        assert_eq!(res.is_shared_library(), shared, "{}", path);
+    }
+}
+
+#[test]
+fn test_dynamic_flags() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Checked with `readelf -d`
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let (flags, flags_1) = res.dynamic_flags();
+    assert!(flags.is_empty());
+    assert!(flags_1.contains(DynamicFlag1::DF_1_PIE));
+    assert_eq!(decode_dynamic_flags_1(flags_1), vec!["DF_1_PIE"]);
+
+    // Linked with `-z now -z nodelete`
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let (flags, flags_1) = res.dynamic_flags();
+    assert_eq!(decode_dynamic_flags(flags), vec!["DF_BIND_NOW"]);
+    assert_eq!(decode_dynamic_flags_1(flags_1), vec!["DF_1_NOW", "DF_1_NODELETE"]);
+    assert!(!res.is_pie());
+}
+
+#[test]
+fn test_init_fini_array() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // A C++ program with a static object, a `constructor` and a `destructor` function
+    let mut file = File::open("test/test_ctors").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        // Checked with `readelf -x .init_array -x .fini_array test/test_ctors` and `nm`
+        let name_at = |address: u64| res.symbols()
+            .into_iter()
+            .find(|sym| sym.value() == address)
+            .map(|sym| sym.name().to_string());
+        let init = res.init_array();
+        assert_eq!(init, vec![0x1190, 0x106c, 0x107b]);
+        let names: Vec<_> = init.iter().filter_map(|&address| name_at(address)).collect();
+        assert_eq!(names, vec!["frame_dummy", "_ZL5earlyv", "_GLOBAL__sub_I_main"]);
+        let fini = res.fini_array();
+        assert_eq!(fini, vec![0x1150, 0x1060]);
+        assert_eq!(name_at(fini[1]), Some("_ZL4latev".to_string()));
+    }
+
+    // Without section headers (`e_shnum` cleared), the dynamic entries are used
+    buf[60] = 0;
+    buf[61] = 0;
+    buf[62] = 0;
+    buf[63] = 0;
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.sections().is_empty());
+        assert_eq!(res.init_array(), vec![0x1190, 0x106c, 0x107b]);
+        assert_eq!(res.fini_array(), vec![0x1150, 0x1060]);
+    }
+
+    // A `DT_INIT_ARRAYSZ` larger than the file, in a segment claiming to be as large, is not
+    // read
+    let (size_entry, load_header) = {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let dynamic = res.dynamic().unwrap();
+        let index = dynamic
+            .iter()
+            .position(|entry| *entry.tag() == DynamicTag::DT_INIT_ARRAYSZ)
+            .unwrap();
+        let dynamic_offset = res.segments()
+            .into_iter()
+            .find(|seg| *seg.segment_type() == SegmentType::PT_DYNAMIC)
+            .unwrap()
+            .phdr()
+            .offset() as usize;
+        let address = dynamic_value(&dynamic, DynamicTag::DT_INIT_ARRAY).unwrap();
+        let load = res.segments()
+            .iter()
+            .position(|seg| {
+                let phdr = seg.phdr();
+                *seg.segment_type() == SegmentType::PT_LOAD
+                    && address >= phdr.vaddr()
+                    && address - phdr.vaddr() < phdr.mem_size()
+            })
+            .unwrap();
+        (dynamic_offset + index * 16 + 8, res.header().phoff() as usize + load * 56)
+    };
+    let huge = 0x7fff_ffff_ffff_0000u64.to_le_bytes();
+    buf[size_entry..size_entry + 8].copy_from_slice(&huge);
+    // `p_memsz`
+    buf[load_header + 40..load_header + 48].copy_from_slice(&huge);
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.init_array().is_empty());
+        assert_eq!(res.fini_array(), vec![0x1150, 0x1060]);
+    }
+
+    // 4 byte pointers
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.init_array(), vec![0x510]);
+    assert_eq!(res.fini_array(), vec![0x4c0]);
+
+    let mut file = File::open("test/test_common.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.init_array().is_empty());
+}
+
+#[test]
+fn test_loader_info() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    // Linked with `-rpath '$ORIGIN/lib:/opt/rustep/lib' -rpath /usr/local/lib -lm`
+    let mut file = File::open("test/test_rpath").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let runpath_tag = {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        // Checked with `readelf -d test/test_rpath`
+        assert_eq!(res.loader_info(), LoaderInfo {
+            interpreter: Some("/lib64/ld-linux-x86-64.so.2".to_string()),
+            rpath: Vec::new(),
+            runpath: vec![
+                "$ORIGIN/lib".to_string(),
+                "/opt/rustep/lib".to_string(),
+                "/usr/local/lib".to_string(),
+            ],
+            needed: vec!["libm.so.6".to_string(), "libc.so.6".to_string()],
+        });
+        // `DT_RUNPATH` is the third dynamic entry
+        res.section(".dynamic").unwrap().shdr().offset() as usize + 2 * 16
+    };
+
+    // Turn it into the older `DT_RPATH`
+    buf[runpath_tag] = 15;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let info = res.loader_info();
+    assert!(info.runpath.is_empty());
+    assert_eq!(info.rpath, vec!["$ORIGIN/lib", "/opt/rustep/lib", "/usr/local/lib"]);
+
+    // A `DT_STRSZ` larger than the file, in a segment claiming to be as large, is not read
+    let (size_entry, load_header) = {
+        let dynamic = res.dynamic().unwrap();
+        let index = dynamic
+            .iter()
+            .position(|entry| *entry.tag() == DynamicTag::DT_STRSZ)
+            .unwrap();
+        let address = dynamic_value(&dynamic, DynamicTag::DT_STRTAB).unwrap();
+        let load = res.segments()
+            .iter()
+            .position(|seg| {
+                let phdr = seg.phdr();
+                *seg.segment_type() == SegmentType::PT_LOAD
+                    && address >= phdr.vaddr()
+                    && address - phdr.vaddr() < phdr.mem_size()
+            })
+            .unwrap();
+        (
+            res.section(".dynamic").unwrap().shdr().offset() as usize + index * 16 + 8,
+            res.header().phoff() as usize + load * 56,
+        )
+    };
+    let mut patched = buf.clone();
+    let huge = 0x7fff_ffff_ffff_0000u64.to_le_bytes();
+    patched[size_entry..size_entry + 8].copy_from_slice(&huge);
+    // `p_memsz`
+    patched[load_header + 40..load_header + 48].copy_from_slice(&huge);
+    let result = parse_elf(&patched).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let info = res.loader_info();
+    assert_eq!(info.interpreter, Some("/lib64/ld-linux-x86-64.so.2".to_string()));
+    assert_eq!(info.needed, vec![String::new(), String::new()]);
+
+    let mut file = File::open("test/test_lib.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let info = res.loader_info();
+    assert_eq!(info.interpreter, None);
+    assert!(info.rpath.is_empty() && info.runpath.is_empty());
+
+    let mut file = File::open("test/test_common.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.loader_info(), LoaderInfo::default());
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn test_content_hash() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let first = {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        res.content_hash()
+    };
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.content_hash(), first);
+
+    // `sha256sum` of the two `PT_LOAD` ranges, 0..0x830 and 0xde0..0x1030
+    let expected = [
+        0x1c, 0xda, 0xf9, 0x05, 0x93, 0x52, 0xac, 0x30, 0x05, 0xe8, 0xdf, 0x69, 0x12, 0xdd, 0x6a, 0x37,
+        0xef, 0xb1, 0xcc, 0x81, 0x9b, 0xff, 0xc1, 0xc2, 0x9b, 0x69, 0xf4, 0x0d, 0xc7, 0xc0, 0x51, 0xa8,
+    ];
+    assert_eq!(first, expected);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_ne!(res.content_hash(), first);
+}
+
+#[test]
+fn test_max_entries() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // Extended numbering: `e_shnum` cleared and the count moved to `sh_size` of the first
+    // section header (at 0x1e40), `e_phnum` set to `PN_XNUM` and the count moved to its `sh_info`
+    let mut extended = buf.clone();
+    extended[60..62].copy_from_slice(&0u16.to_le_bytes());
+    extended[0x1e40 + 32..0x1e40 + 40].copy_from_slice(&30u64.to_le_bytes());
+    extended[56..58].copy_from_slice(&(PN_XNUM as u16).to_le_bytes());
+    extended[0x1e40 + 44..0x1e40 + 48].copy_from_slice(&9u32.to_le_bytes());
+    let result = parse_elf(&extended).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.sections().len(), 30);
+    assert_eq!(res.segments().len(), 9);
+    assert_eq!(res.raw_section_headers().len(), 30 * 64);
+
+    // A billion sections is rejected before anything is parsed
+    let mut huge = extended.clone();
+    huge[0x1e40 + 32..0x1e40 + 40].copy_from_slice(&1_000_000_000u64.to_le_bytes());
+    assert_eq!(parse_elf(&huge).err(), Some(RustepError::TooManyEntries(1_000_000_000)));
+    let unbounded = ParseOptions { max_entries: usize::max_value(), ..ParseOptions::default() };
+    match parse_elf_with(&huge, &unbounded) {
+        Err(RustepError::Parse { context, .. }) => assert_eq!(context, "section header table"),
+        _ => panic!("a billion section headers parsed"),
+    }
+
+    // The budget covers the program headers as well
+    let capped = ParseOptions { max_entries: 8, ..ParseOptions::default() };
+    let mut no_sections = buf.clone();
+    no_sections[60..62].copy_from_slice(&0u16.to_le_bytes());
+    assert_eq!(parse_elf_with(&no_sections, &capped).err(), Some(RustepError::TooManyEntries(9)));
+    let capped = ParseOptions { max_entries: 30, ..ParseOptions::default() };
+    assert!(parse_elf_with(&buf, &capped).is_ok());
+}
+
+#[test]
+fn test_table_capacity() {
+    use std::{fs::File, io::prelude::*};
+
+    for path in ["test/test", "test/test32", "test/test_debug.o"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.sections().len(), res.header().shnum() as usize, "{}", path);
+        assert_eq!(res.segments().len(), res.header().phnum() as usize, "{}", path);
+        // Reserved once, from the parsed tables
+        match result {
+            Executable::Elf32(ref elf) => {
+                assert_eq!(elf.sections.capacity(), elf.sections.len(), "{}", path);
+                assert_eq!(elf.segments.capacity(), elf.segments.len(), "{}", path);
+            },
+            Executable::Elf64(ref elf) => {
+                assert_eq!(elf.sections.capacity(), elf.sections.len(), "{}", path);
+                assert_eq!(elf.segments.capacity(), elf.segments.len(), "{}", path);
+                assert_eq!(elf.symbols.capacity(), elf.symbols.len(), "{}", path);
+            },
+        }
+    }
+}
+
+#[test]
+fn test_shstrndx_not_strtab() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+
+    // e_shstrndx (0x3e) redirected to `.text`
+    let mut redirected = buf.clone();
+    redirected[0x3e..0x40].copy_from_slice(&13u16.to_le_bytes());
+    assert_eq!(
+        parse_elf_with(&redirected, &strict).err(),
+        Some(RustepError::InvalidStringTable(13))
+    );
+    let result = parse_elf(&redirected).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.sections().len(), 30);
+    assert!(res.sections().iter().all(|sec| sec.name().is_empty()));
+    // Symbol names come from `.strtab`, not from `e_shstrndx`
+    assert!(res.symbols().iter().any(|sym| sym.name() == "main"));
+
+    // `SHN_UNDEF` just means there are no names
+    let mut undefined = buf.clone();
+    undefined[0x3e..0x40].copy_from_slice(&0u16.to_le_bytes());
+    let result = parse_elf_with(&undefined, &strict).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.section(".text").is_none());
+}
+
+#[test]
+fn test_section_header_strtab() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let shstrtab = res.section_header_strtab().unwrap();
+    assert_eq!(shstrtab.name(), ".shstrtab");
+    assert_eq!(shstrtab.index(), 29);
+    for name in [".text", ".dynsym", ".got", ".shstrtab"].iter() {
+        let entry = format!("{}\0", name);
+        assert!(shstrtab.data().windows(entry.len()).any(|w| w == entry.as_bytes()), "{}", name);
+    }
+
+    // `e_shstrndx` set to `SHN_XINDEX` (0x3e), the index moved to `sh_link` of the first section
+    // header (at 0x1e40)
+    let mut extended = buf.clone();
+    extended[0x3e..0x40].copy_from_slice(&(SHN_XINDEX as u16).to_le_bytes());
+    extended[0x1e40 + 40..0x1e40 + 44].copy_from_slice(&29u32.to_le_bytes());
+    let result = parse_elf(&extended).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.section_header_strtab().unwrap().index(), 29);
+    assert_eq!(res.section(".text").unwrap().index(), 13);
+    assert!(res.validate().is_ok());
+
+    // Files without section headers have none
+    let mut no_sections = buf.clone();
+    for i in (0x28..0x30).chain(0x3c..0x40) {
+        no_sections[i] = 0;
+    }
+    let result = parse_elf(&no_sections).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.section_header_strtab().is_none());
+}
+
+#[test]
+fn test_format_symbols() {
+    use std::{fs::File, io::prelude::*};
+
+    // `test/test.nm` is the output of `nm test/test`
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let mut expected = String::new();
+    File::open("test/test.nm").unwrap().read_to_string(&mut expected).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.format_symbols(), expected);
+
+    let mut file = File::open("test/test_common.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(
+        res.format_symbols(),
+        "0000000000000004 C common_var\n0000000000000000 T main\n"
+    );
+
+    // Stripped, nothing to list
+    let mut file = File::open("test/test_stripped").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.format_symbols(), "");
+}
+
+#[test]
+fn test_exported_symbol_names() {
+    use std::{fs::File, io::prelude::*};
+
+    // Checked with `nm -D --defined-only`
+    let mut file = File::open("test/test_hash.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let mut expected: Vec<String> = (0..12).map(|i| format!("func_{}", i)).collect();
+    expected.push("shared_counter".to_string());
+    expected.sort();
+    assert_eq!(res.exported_symbol_names(), expected);
+
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.exported_symbol_names(), vec!["answer"]);
+
+    // Only undefined and weak undefined dynamic symbols, the executable exports nothing
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.dynamic_symbols().len(), 7);
+    assert!(res.exported_symbol_names().is_empty());
+}
+
+#[test]
+fn test_parse_options() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // `test/test` has nothing unknown, strict mode accepts it
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+    assert!(parse_elf_with(&buf, &strict).is_ok());
+
+    let no_names = ParseOptions { resolve_names: false, ..ParseOptions::default() };
+    let result = parse_elf_with(&buf, &no_names).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.sections().iter().all(|sec| sec.name().is_empty()));
+        assert!(res.symbols().iter().all(|sym| sym.name().is_empty()));
+        assert_eq!(res.symbols().len(), 65);
+    }
+
+    let no_symbols = ParseOptions { parse_symbols: false, ..ParseOptions::default() };
+    let result = parse_elf_with(&buf, &no_symbols).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.symbols().is_empty());
+        assert!(res.dynamic_symbols().is_empty());
+        assert_eq!(res.section(".text").unwrap().name(), ".text");
+    }
+
+    // `test/test` has 30 section headers
+    let capped = ParseOptions { max_sections: 29, ..ParseOptions::default() };
+    assert!(parse_elf_with(&buf, &capped).is_err());
+    let capped = ParseOptions { max_sections: 30, ..ParseOptions::default() };
+    assert!(parse_elf_with(&buf, &capped).is_ok());
+
+    // Unknown section type (see `test_unknown_section_type`) and unknown flag bits are only
+    // rejected in strict mode
+    let mut file = File::open("test/test_arm.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    assert!(parse_elf(&buf).is_ok());
+    match parse_elf_with(&buf, &strict) {
+        Err(e) => assert_eq!(
+            e,
+            RustepError::SectionType(0x70000001)
+        ),
+        Ok(_) => panic!("unknown section type accepted in strict mode"),
+    }
+}
+
+#[test]
+fn test_section_filter() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    fn text_only(name: &str, _: &ElfSectionHeader) -> bool {
+        name == ".text"
+    }
+    let options = ParseOptions { section_filter: Some(text_only), ..ParseOptions::default() };
+    let filtered = parse_elf_with(&buf, &options).unwrap();
+    let filtered: &ElfFormat = (&filtered).try_into().expect("unable to convert");
+    let full = parse_elf(&buf).unwrap();
+    let full: &ElfFormat = (&full).try_into().expect("unable to convert");
+
+    let sections = filtered.sections();
+    assert_eq!(sections.len(), 1);
+    // `.text` keeps its index in the header table
+    assert_eq!(sections[0].index(), 13);
+    assert_eq!(sections[0].data(), full.section(".text").unwrap().data());
+    assert_eq!(filtered.section_by_index(13).map(|sec| sec.name()), Some(".text"));
+    assert!(filtered.section_by_index(0).is_none());
+    let code_segment = filtered.segments()[2];
+    let names: Vec<&str> = filtered.sections_in_segment(code_segment)
+        .iter()
+        .map(|sec| sec.name())
+        .collect();
+    assert_eq!(names, vec![".text"]);
+    assert_eq!(full.section_by_index(13).map(|sec| sec.name()), Some(".text"));
+    // `.symtab` is filtered out, so are the symbols
+    assert!(filtered.symbols().is_empty());
+    assert_eq!(filtered.section_count(), 30);
+    // The rest of the file is not known
+    let mut out = Vec::new();
+    assert_eq!(
+        filtered.write(&mut out),
+        Err(RustepError::FilteredSections { kept: 1, count: 30 })
+    );
+    assert!(out.is_empty());
+
+    // The string table of the symbols is found by its index even when it is filtered out
+    fn symtab_only(name: &str, _: &ElfSectionHeader) -> bool {
+        name == ".symtab"
+    }
+    let options = ParseOptions { section_filter: Some(symtab_only), ..ParseOptions::default() };
+    let filtered = parse_elf_with(&buf, &options).unwrap();
+    let filtered: &ElfFormat = (&filtered).try_into().expect("unable to convert");
+    assert_eq!(filtered.sections().len(), 1);
+    let names = |elf: &ElfFormat| -> Vec<String> {
+        elf.symbols().iter().map(|sym| sym.name().to_string()).collect()
+    };
+    assert_eq!(names(filtered), names(full));
+    let main = filtered.symbols().into_iter().find(|sym| sym.name() == "main").unwrap();
+    assert!(filtered.symbol_location(main).is_none());
+}
+
+#[test]
+fn test_invalid_section_name() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // Section headers start at 0x1e40, point `sh_name` of section 1 (`.interp`) far past the end
+    // of `.shstrtab`
+    buf[0x1e40 + 64 + 2] = 0xff;
+    let result = parse_elf(&buf).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.sections()[1].name(), "");
+        assert_eq!(res.sections()[2].name(), ".note.ABI-tag");
+    }
+
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+    match parse_elf_with(&buf, &strict) {
+        Err(e) => assert_eq!(
+            e,
+            RustepError::InvalidStringIndex(0xff0000 + 0x1b)
+        ),
+        Ok(_) => panic!("out of range section name accepted in strict mode"),
+    }
+}
+
+#[test]
+fn test_parse_error_offset() {
+    use std::error::Error;
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let parse_error = |input: &[u8]| match parse_elf(input) {
+        Err(e) => e,
+        Ok(_) => panic!("truncated file accepted"),
+    };
+    // Section headers start at 0x1e40, cut the file in the middle of the first one
+    let error = parse_error(&buf[..0x1e40 + 10]);
+    match error {
+        RustepError::Parse { offset, context, cause } => {
+            assert_eq!((offset, context), (0x1e40, "section header table"));
+            match cause {
+                ParseCause::Incomplete(_) => (),
+                cause => panic!("unexpected cause {:?}", cause),
+            }
+        },
+        error => panic!("unexpected error {:?}", error),
+    }
+    assert_eq!(error.to_string(), "Parsing error at offset 0x1e40 (section header table)");
+    assert!(error.source().unwrap().to_string().starts_with("Not enough byte"));
+    assert!(RustepError::NotElf.source().is_none());
+}
+
+#[test]
+fn test_file_too_small() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let parse_error = |input: &[u8]| match parse_elf(input) {
+        Err(e) => e,
+        Ok(_) => panic!("truncated file accepted"),
+    };
+    // The class byte is there, so the 64-bit header size is known
+    assert_eq!(parse_error(&buf[..10]), RustepError::FileTooSmall { needed: 64, got: 10 });
+    assert_eq!(parse_error(&buf[..4]), RustepError::FileTooSmall { needed: 52, got: 4 });
+}
+
+#[test]
+fn test_truncated_sections() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test_ctors").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // The section header table is last, move it to the padding before the code at 0x1000 so it
+    // survives the truncation (checked with `readelf -S test/test_ctors`)
+    let shoff = 0x3838;
+    let table = buf.split_off(shoff);
+    buf[0x800..0x800 + table.len()].copy_from_slice(&table);
+    buf[0x28..0x30].copy_from_slice(&[0, 8, 0, 0, 0, 0, 0, 0]);
+    // Cut the last 100 bytes of `.shstrtab`, which ends at 0x3835
+    buf.truncate(shoff - 100);
+
+    {
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let truncated = res.truncated_sections();
+        assert_eq!(truncated.len(), 1);
+        assert_eq!(truncated[0].index(), 31);
+        assert_eq!(truncated[0].data(), &buf[0x3709..]);
+        // The names found in what is left of `.shstrtab` are still resolved
+        assert_eq!(res.section(".text").unwrap().index(), 15);
+        assert!(res.symbols().iter().any(|sym| sym.name() == "main"));
+        let warning = ValidationWarning::SectionOutOfBounds {
+            index: 31,
+            end: 0x3835,
+            file_size: buf.len() as u64,
+        };
+        assert!(res.validate().unwrap_err().contains(&warning));
+    }
+
+    // Strict mode does not know `PT_GNU_PROPERTY` (index 9), turn it into a `PT_NULL`
+    buf[0x40 + 9 * 56..0x40 + 9 * 56 + 4].copy_from_slice(&[0, 0, 0, 0]);
+    let strict = ParseOptions { strict: true, ..ParseOptions::default() };
+    let parse_error = |input: &[u8]| match parse_elf_with(input, &strict) {
+        Err(e) => e,
+        Ok(_) => panic!("truncated file accepted"),
+    };
+    assert_eq!(parse_error(&buf), RustepError::SectionOutOfBounds {
+        index: 31,
+        end: 0x3835,
+        file_size: buf.len() as u64,
+    });
+    // The last `PT_LOAD` (index 5) holds `.init_array` to `.data` and ends at 0x3028
+    buf.truncate(0x3000);
+    assert_eq!(parse_error(&buf), RustepError::SegmentOutOfBounds {
+        index: 5,
+        end: 0x3028,
+        file_size: 0x3000,
+    });
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.segments()[5].data().len(), 0x3000 - 0x2da8);
+    // `.data` (index 26) is cut, `.shstrtab` is entirely gone so the names are left empty
+    assert!(res.truncated_sections().iter().any(|sec| sec.index() == 26));
+    assert!(res.sections().iter().all(|sec| sec.name().is_empty()));
+}
+
+#[test]
+fn test_no_sections_no_segments() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // e_shoff (0x28), e_shnum (0x3c) and e_shstrndx (0x3e) all zeroed, the way some packers
+    // leave their output
+    let mut no_sections = buf.clone();
+    for i in (0x28..0x30).chain(0x3c..0x40) {
+        no_sections[i] = 0;
+    }
+    let result = parse_elf(&no_sections).unwrap();
+    {
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert!(res.sections().is_empty());
+        assert_eq!(res.segments().len(), 9);
+        assert!(res.section(".text").is_none());
+        assert!(res.symbols().is_empty());
+        assert!(res.is_stripped());
+        assert_eq!(res.format_section_table().lines().count(), 1);
+    }
+
+    // Only the ELF header is left, e_phoff (0x20) and e_phnum (0x38) zeroed as well
+    let mut header_only = no_sections[..64].to_vec();
+    for i in (0x20..0x28).chain(0x38..0x3a) {
+        header_only[i] = 0;
+    }
+    let result = parse_elf(&header_only).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.sections().is_empty());
+    assert!(res.segments().is_empty());
+    assert_eq!(res.entry_bytes(16), None);
+    assert_eq!(res.dynamic().unwrap(), vec![]);
+    assert!(res.plt_entries().is_empty());
+    assert_eq!(res.validate(), Ok(()));
+}
+
+#[test]
+fn test_phdr_round_trip() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for path in ["test/test", "test/test32"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let hdr = res.header();
+        assert!(hdr.phnum() > 0);
+        for (i, seg) in res.segments().iter().enumerate() {
+            let start = (hdr.phoff() + hdr.phentsize() * i as u64) as usize;
+            let end = start + hdr.phentsize() as usize;
+            assert_eq!(seg.phdr().to_bytes(), &buf[start..end], "{} segment {}", path, i);
+        }
+    }
+}
+
+#[test]
+fn test_write_round_trip() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for path in ["test/test", "test/test32", "test/test_debug.o", "test/test_tls"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let mut out = Vec::new();
+        res.write(&mut out).unwrap();
+        assert!(out == buf, "{} differs after a round trip", path);
+    }
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    // The second `PT_LOAD` (program header 3) moved far past the end of the file is kept with no
+    // contents, there is nothing to write for it
+    let mut moved = buf.clone();
+    moved[0xe8 + 8..0xe8 + 16].copy_from_slice(&0xffff_ffff_ffff_fff0u64.to_le_bytes());
+    let result = parse_elf(&moved).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let mut out = Vec::new();
+    res.write(&mut out).unwrap();
+    assert!(out == moved);
+
+    // The tables were read as arrays of headers, other entry sizes cannot be written back
+    for &(field, size, expected) in [(54, 0x1000u16, 56u64), (58, 0x48, 64)].iter() {
+        let mut spread = buf.clone();
+        spread[field..field + 2].copy_from_slice(&size.to_le_bytes());
+        let result = parse_elf(&spread).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        let mut out = Vec::new();
+        assert_eq!(res.write(&mut out), Err(RustepError::SizeMismatch(expected, size as u64)));
+        assert!(out.is_empty());
+    }
+}
+
+#[test]
+fn test_imports_exports() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    let mut file = File::open("test/test_lib.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf --dyn-syms test/test_lib.so`
+    let imports: Vec<&str> = res.imports().iter().map(|sym| sym.name()).collect();
+    assert_eq!(imports, vec![
+        "__cxa_finalize",
+        "_ITM_registerTMCloneTable",
+        "_ITM_deregisterTMCloneTable",
+        "__gmon_start__",
+    ]);
+    let exports: Vec<&str> = res.exports().iter().map(|sym| sym.name()).collect();
+    assert_eq!(exports, vec!["answer"]);
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.imports().iter().any(|sym| sym.name() == "printf"));
+    assert!(res.exports().iter().all(|sym| sym.name() != "printf"));
+}
+
+#[test]
+fn test_class_and_endianness() {
+    use std::fs::File;
+    use std::io::prelude::*;
+
+    for &(path, is_64bit) in [("test/test", true), ("test/test32", false)].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        {
+            let result = parse_elf(&buf).unwrap();
+            let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+            assert_eq!(res.is_64bit(), is_64bit);
+            assert_eq!(res.endianness(), Endianness::Little);
+        }
+
+        // Only `e_ident[EI_DATA]` is looked at
+        buf[5] = 2;
+        let result = parse_elf(&buf).unwrap();
+        let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+        assert_eq!(res.endianness(), Endianness::Big);
+    }
+}
+
+#[test]
+fn test_decode_flags() {
+    use std::{fs::File, io::prelude::*};
+
+    let mut file = File::open("test/test_arm.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let hdr = res.header();
+    assert_eq!(hdr.flags(), 0x05000000);
+    assert_eq!(decode_flags(hdr.machine().unwrap(), hdr.flags()), vec!["EF_ARM_EABI_VER5"]);
+    assert_eq!(
+        decode_flags(ElfMachine::ARM, 0x06800000),
+        vec!["EF_ARM_BE8", "0x6000000"]
+    );
+
+    let mut file = File::open("test/test_riscv64.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let hdr = res.header();
+    assert_eq!(decode_flags(hdr.machine().unwrap(), hdr.flags()), vec!["EF_RISCV_FLOAT_ABI_SOFT"]);
+    assert_eq!(
+        decode_flags(ElfMachine::RISCV, 0x5),
+        vec!["EF_RISCV_RVC", "EF_RISCV_FLOAT_ABI_DOUBLE"]
+    );
+
+    assert_eq!(
+        decode_flags(ElfMachine::MIPS, 0x70001007),
+        vec!["EF_MIPS_NOREORDER", "EF_MIPS_PIC", "EF_MIPS_CPIC", "EF_MIPS_ABI_O32", "EF_MIPS_ARCH_32R2"]
+    );
+    assert_eq!(decode_flags(ElfMachine::X86_64, 0), Vec::<String>::new());
+    assert_eq!(decode_flags(ElfMachine::X86_64, 0x10), vec!["0x10"]);
+}
+
+#[test]
+fn test_to_owned() {
+    use std::{fs::File, io::prelude::*};
+    use format::executable::ExecutableOwned;
+
+    fn parse_owned(path: &str) -> ExecutableOwned {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        // `buf` is dropped when returning, so the result must not borrow it
+        let result = parse_elf(&buf).unwrap();
+        result.to_owned()
+    }
+    fn assert_send<T: Send>(_: &T) {}
+
+    for path in ["test/test", "test/test32"].iter() {
+        let owned = parse_owned(path);
+        assert_send(&owned);
+
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        let borrowed = parse_elf(&buf).unwrap();
+
+        let owned: &ElfFormat = (&owned).try_into().expect("unable to convert");
+        let borrowed: &ElfFormat = (&borrowed).try_into().expect("unable to convert");
+        assert_eq!(owned.header().entry(), borrowed.header().entry());
+        assert_eq!(owned.sections().len(), borrowed.sections().len());
+        for (o, b) in owned.sections().iter().zip(borrowed.sections().iter()) {
+            assert_eq!(o.name(), b.name());
+            assert_eq!(o.data(), b.data());
+        }
+        assert_eq!(owned.segments().len(), borrowed.segments().len());
+        for (o, b) in owned.segments().iter().zip(borrowed.segments().iter()) {
+            assert_eq!(o.data(), b.data());
+        }
+    }
+}
+
+// parse elf tests
+#[test]
+fn test_parse_elf_wrong_class() {
+    match parse_elf(b"\x7fELF\x05") {
+        Err(e) => assert_eq!(
+            e,
+            RustepError::UnsupportedElfClass(5)
+        ),
+        _ => panic!("parse elf with class 5 succeed, which cannot happen"),
+    }
+    // Unknown classes may be future ones, `ELFCLASSNONE` is never valid
+    assert_eq!(parse_elf(b"\x7fELF\x09").err(), Some(RustepError::UnsupportedElfClass(9)));
+    assert_eq!(parse_elf(b"\x7fELF\x00").err(), Some(RustepError::InvalidElfClass));
+    assert_eq!(parse_elf_headers(b"\x7fELF\x00").err(), Some(RustepError::InvalidElfClass));
+    assert_eq!(
+        parse_elf_headers(b"\x7fELF\x09").err(),
+        Some(RustepError::UnsupportedElfClass(9))
+    );
+}
+
+// ############### Elf Class ####################
+
+/// Elf class means to differ in arch, we must get that ahead to determine which type to be used.
+named!(parse_elf_class<&[u8], u8>,
+    do_parse!(
+        tag!("\x7fELF") >> // Elf ident
+        data: le_u8 >>
+        (data)
+    )
+);
+
+#[test]
+fn test_parse_elf_class() {
+    // 32 class
+    assert_eq!(parse_elf_class(b"\x7fELF\x01"), Done(b"" as &[u8], 1));
+
+    // 64 class
+    assert_eq!(parse_elf_class(b"\x7fELF\x02"), Done(b"" as &[u8], 2));
+
+    use nom::ErrorKind;
+    // error
+    assert_eq!(
+        parse_elf_class(b"FLE\x01"),
+        Error(error_position!(ErrorKind::Tag, b"FLE\x01" as &[u8]))
+    );
+}
+
+// ############### Elf Header 32 ################
+
+fn parse_e_ident(input: &[u8]) -> IResult<&[u8], [u8; 16]> {
+    let res = take!(input, 16);
+    let mut v = [0; 16];
+    if let Done(_i, val) = res {
+        v.clone_from_slice(val);
+        Done(_i, v)
+    } else if let Error(e) = res {
+        Error(e)
+    } else if let Incomplete(need) = res {
+        Incomplete(need)
+    } else {
+        unreachable!()
+    }
+}
+
+named!(parse_elf_header32<&[u8], Elf32_Ehdr>,
+    do_parse!(
+        e_ident: parse_e_ident >>
+        e_type: le_u16 >>
+        e_machine: le_u16 >>
+        e_version: le_u32 >>
+        e_entry: le_u32 >>
+        e_phoff: le_u32 >>
+        e_shoff: le_u32 >>
+        e_flags: le_u32 >>
+        e_ehsize: le_u16 >>
+        e_phentsize: le_u16 >>
+        e_phnum: le_u16 >>
+        e_shentsize: le_u16 >>
+        e_shnum: le_u16 >>
+        e_shstrndx: le_u16 >>
+        (Elf32_Ehdr {
+            e_ident: e_ident,
+            e_type: e_type,
+            e_machine: e_machine,
+            e_version: e_version,
+            e_entry: e_entry,
+            e_phoff: e_phoff,
+            e_shoff: e_shoff,
+            e_flags: e_flags,
+            e_ehsize: e_ehsize,
+            e_phentsize: e_phentsize,
+            e_phnum: e_phnum,
+            e_shentsize: e_shentsize,
+            e_shnum: e_shnum,
+            e_shstrndx: e_shstrndx
+        })
+    )
+);
+
+#[test]
+fn test_parse_elf_header32() {
+    use std::{fs::File, io::prelude::*};
+
+    let file = File::open("test/test32").unwrap();
+    let mut buf = [0; 0x34];
+    let mut handle = file.take(0x34);
+    handle.read(&mut buf).unwrap();
+    let res = parse_elf_header32(&buf);
+
+    if let Done(_, hdr) = res {
+        // These test values are parsed by 010 editor, and regarded trustable
+
+        // \x7fELF id test
+        assert_eq!(hdr.e_ident[0], 0x7fu8);
+        assert_eq!(hdr.e_ident[1], 0x45u8);
+        assert_eq!(hdr.e_ident[2], 0x4cu8);
+        assert_eq!(hdr.e_ident[3], 0x46u8);
+
+        // ET_DYN = 3
+        assert_eq!(hdr.e_type, 3);
+
+        // EM_386 = 3
+        assert_eq!(hdr.e_machine, 3);
+
+        // EV_CURRENT = 1
+        assert_eq!(hdr.e_version, 1);
+
+        assert_eq!(hdr.e_entry, 0x3e0);
+        assert_eq!(hdr.e_phoff, 52);
+        assert_eq!(hdr.e_shoff, 7372);
+        assert_eq!(hdr.e_flags, 0);
+        assert_eq!(hdr.e_ehsize, 52);
+        assert_eq!(hdr.e_phentsize, 32);
+        assert_eq!(hdr.e_phnum, 9);
+        assert_eq!(hdr.e_shentsize, 40);
+        assert_eq!(hdr.e_shnum, 31);
+        assert_eq!(hdr.e_shstrndx, 30);
+    } else if let Error(err) = res {
+        panic!(err.to_string());
+    } else if let Incomplete(need) = res {
+        match need {
+            Size(size) => panic!("Incomplete, needs size {}", size),
+            Unknown => panic!("Incomplete, needs size unknown"),
+        }
+    } else {
+        unreachable!();
+    }
+}
+
+// ############### Elf Header 64 ################
+
+named!(parse_elf_header64<&[u8], Elf64_Ehdr>,
+    do_parse!(
+        e_ident: parse_e_ident >>
+        e_type: le_u16 >>
+        e_machine: le_u16 >>
+        e_version: le_u32 >>
+        e_entry: le_u64 >>
+        e_phoff: le_u64 >>
+        e_shoff: le_u64 >>
+        e_flags: le_u32 >>
+        e_ehsize: le_u16 >>
+        e_phentsize: le_u16 >>
+        e_phnum: le_u16 >>
+        e_shentsize: le_u16 >>
+        e_shnum: le_u16 >>
+        e_shstrndx: le_u16 >>
+        (Elf64_Ehdr {
+            e_ident: e_ident,
+            e_type: e_type,
+            e_machine: e_machine,
+            e_version: e_version,
+            e_entry: e_entry,
+            e_phoff: e_phoff,
+            e_shoff: e_shoff,
+            e_flags: e_flags,
+            e_ehsize: e_ehsize,
+            e_phentsize: e_phentsize,
+            e_phnum: e_phnum,
+            e_shentsize: e_shentsize,
+            e_shnum: e_shnum,
+            e_shstrndx: e_shstrndx
+        })
+    )
+);
+
+#[test]
+fn test_parse_elf_header64() {
+    use std::{fs::File, io::prelude::*};
+
+    let file = File::open("test/test").unwrap();
+    let mut buf = [0; 0x40];
+    let mut handle = file.take(0x40);
+    handle.read(&mut buf).unwrap();
+    let res = parse_elf_header64(&buf);
+
+    if let Done(_i, hdr) = res {
+        assert_eq!(hdr.e_ident[0], 0x7f);
+        assert_eq!(hdr.e_ident[1], 0x45);
+        assert_eq!(hdr.e_ident[2], 0x4c);
+        assert_eq!(hdr.e_ident[3], 0x46);
+
+        // ET_DYN = 3
+        assert_eq!(hdr.e_type, 3);
+
+        // EM_X86_64 = 62
+        assert_eq!(hdr.e_machine, 62);
+
+        // EV_CURRENT = 1
+        assert_eq!(hdr.e_version, 1);
+
+        assert_eq!(hdr.e_entry, 0x540);
+        assert_eq!(hdr.e_phoff, 64);
+        assert_eq!(hdr.e_shoff, 7744);
+        assert_eq!(hdr.e_flags, 0);
+        assert_eq!(hdr.e_ehsize, 64);
+        assert_eq!(hdr.e_phentsize, 56);
+        assert_eq!(hdr.e_phnum, 9);
+        assert_eq!(hdr.e_shentsize, 64);
+        assert_eq!(hdr.e_shnum, 30);
+        assert_eq!(hdr.e_shstrndx, 29);
+    } else if let Error(err) = res {
+        panic!(err.to_string());
+    } else if let Incomplete(need) = res {
+        match need {
+            Size(size) => {
+                panic!("incomplete, needs size {}", size);
+            }
+            Unknown => {
+                panic!("incomplete file, needs size unknown");
+            }
+        }
+    } else {
+        unreachable!();
+    }
+}
+
+// ############### Elf Program Header 32 ################
+
+/// Parses a single elf program table, 32-bit version. `p_flags` comes after `p_memsz` here, see
+/// `ElfSegmentHeader::to_bytes` for the inverse.
+named!(parse_elf_prog_header32<&[u8], Elf32_Phdr>,
+    do_parse!(
+        p_type: le_u32 >>
+        p_offset: le_u32 >>
+        p_vaddr: le_u32 >>
+        p_paddr: le_u32 >>
+        p_filesz: le_u32 >>
+        p_memsz: le_u32 >>
+        p_flags: le_u32 >>
+        p_align: le_u32 >>
+        (Elf32_Phdr {
+            p_type: p_type,
+            p_offset: p_offset,
+            p_vaddr: p_vaddr,
+            p_paddr: p_paddr,
+            p_filesz: p_filesz,
+            p_memsz: p_memsz,
+            p_flags: p_flags,
+            p_align: p_align
+        })
+    )
+);
+
+// ############### Elf Program Header 64 ################
+/// Parses a single elf program table, 64-bit version. `p_flags` is moved right after `p_type` to
+/// keep the 64-bit fields aligned, unlike the 32-bit layout.
+named!(parse_elf_prog_header64<&[u8], Elf64_Phdr>,
+    do_parse!(
+        p_type: le_u32 >>
+        p_flags: le_u32 >>
+        p_offset: le_u64 >>
+        p_vaddr: le_u64 >>
+        p_paddr: le_u64 >>
+        p_filesz: le_u64 >>
+        p_memsz: le_u64 >>
+        p_align: le_u64 >>
+        (Elf64_Phdr {
+            p_type: p_type,
+            p_flags: p_flags,
+            p_offset: p_offset,
+            p_vaddr: p_vaddr,
+            p_paddr: p_paddr,
+            p_filesz: p_filesz,
+            p_memsz: p_memsz,
+            p_align: p_align,
+        })
+    )
+);
+
+// ############### Elf Section Header 32 ################
+named!(parse_elf_section_header32<&[u8], Elf32_Shdr>,
+    do_parse!(
+        sh_name: le_u32 >>
+        sh_type: le_u32 >>
+        sh_flags: le_u32 >>
+        sh_addr: le_u32 >>
+        sh_offset: le_u32 >>
+        sh_size: le_u32 >>
+        sh_link: le_u32 >>
+        sh_info: le_u32 >>
+        sh_addralign: le_u32 >>
+        sh_entsize: le_u32 >>
+        (Elf32_Shdr {
+            sh_name: sh_name,
+            sh_type: sh_type,
+            sh_flags: sh_flags,
+            sh_addr: sh_addr,
+            sh_offset: sh_offset,
+            sh_size: sh_size,
+            sh_link: sh_link,
+            sh_info: sh_info,
+            sh_addralign: sh_addralign,
+            sh_entsize: sh_entsize
+        })
+    )
+);
+
+// ############### Elf Section Header 64 ################
+named!(parse_elf_section_header64<&[u8], Elf64_Shdr>,
+    do_parse!(
+        sh_name: le_u32 >>
+        sh_type: le_u32 >>
+        sh_flags: le_u64 >>
+        sh_addr: le_u64 >>
+        sh_offset: le_u64 >>
+        sh_size: le_u64 >>
+        sh_link: le_u32 >>
+        sh_info: le_u32 >>
+        sh_addralign: le_u64 >>
+        sh_entsize: le_u64 >>
+        (Elf64_Shdr {
+            sh_name: sh_name,
+            sh_type: sh_type,
+            sh_flags: sh_flags,
+            sh_addr: sh_addr,
+            sh_offset: sh_offset,
+            sh_size: sh_size,
+            sh_link: sh_link,
+            sh_info: sh_info,
+            sh_addralign: sh_addralign,
+            sh_entsize: sh_entsize
+        })
+    )
+);
@@ -0,0 +1,117 @@
+//! Notes (`SHT_NOTE` sections and `PT_NOTE` segments).
+use std::str;
+use nom::{IResult::*, *};
+
+/// A single note entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Note<'a> {
+    /// Owner of the note, e.g. `GNU` or `CORE`, without the terminating NUL
+    pub name: &'a str,
+    /// `n_type`, its meaning depends on the owner
+    pub note_type: u32,
+    /// The descriptor, i.e. the payload of the note
+    pub desc: &'a [u8],
+    /// Where the note was read from
+    pub origin: NoteOrigin<'a>,
+}
+
+/// The view of the file a note was read from. Loaded notes are usually found in both a
+/// section and a segment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NoteOrigin<'a> {
+    /// A `SHT_NOTE` section, with its name
+    Section(&'a str),
+    /// A `PT_NOTE` segment, with its index in the program header table
+    Segment(usize),
+}
+
+impl<'a> Note<'a> {
+    /// Whether both notes have the same owner, type and descriptor, wherever they come from.
+    pub fn same_content(&self, other: &Note) -> bool {
+        self.name == other.name && self.note_type == other.note_type && self.desc == other.desc
+    }
+}
+
+/// Splits the content of a note section or segment into its entries. `align` is the alignment
+/// of the section or segment: entries are padded to 4 bytes, except in 8 aligned ones (used by
+/// `.note.gnu.property`). Parsing stops at the first malformed entry.
+pub(crate) fn parse_notes<'a>(data: &'a [u8], align: u64, origin: NoteOrigin<'a>) -> Vec<Note<'a>> {
+    let align = if align == 8 { 8 } else { 4 };
+    let padded = |size: usize| (size + align - 1) & !(align - 1);
+    let mut notes = Vec::new();
+    let mut rest = data;
+    while let Done(_, (name_size, desc_size, note_type)) = parse_note_header(rest) {
+        let (name_size, desc_size) = (name_size as usize, desc_size as usize);
+        // Offsets from the start of the entry, the 12 bytes header included
+        let desc_start = padded(12 + name_size);
+        let desc_end = desc_start + desc_size;
+        if desc_end > rest.len() {
+            break;
+        }
+        let name = match str::from_utf8(&rest[12..12 + name_size]) {
+            Ok(name) => name.trim_end_matches('\0'),
+            Err(_) => break,
+        };
+        notes.push(Note {
+            name: name,
+            note_type: note_type,
+            desc: &rest[desc_start..desc_end],
+            origin: origin,
+        });
+        rest = &rest[padded(desc_end).min(rest.len())..];
+    }
+
+    notes
+}
+
+// ############### Note header, the same for both classes ################
+named!(parse_note_header<&[u8], (u32, u32, u32)>,
+    tuple!(le_u32, le_u32, le_u32)
+);
+
+#[test]
+fn test_notes() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -n test/test`
+    let notes = res.notes();
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].name, "GNU");
+    assert_eq!(notes[0].note_type, 1);
+    assert_eq!(notes[0].desc, &[0, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(notes[1].name, "GNU");
+    assert_eq!(notes[1].note_type, 3);
+    assert_eq!(notes[1].desc.len(), 20);
+    assert_eq!(notes[1].origin, NoteOrigin::Segment(5));
+
+    // The build id is both in `.note.gnu.build-id` and in the `PT_NOTE` segment
+    let from_sections = res.notes_from_sections();
+    let from_segments = res.notes_from_segments();
+    assert_eq!(from_sections.len(), 2);
+    assert_eq!(from_segments.len(), 2);
+    assert_eq!(from_sections[1].origin, NoteOrigin::Section(".note.gnu.build-id"));
+    assert!(from_sections[1].same_content(&from_segments[1]));
+    assert_ne!(from_sections[1], from_segments[1]);
+
+    // Without program headers, only the sections are left
+    let mut file = File::open("test/test_note.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.notes_from_segments().is_empty());
+    assert_eq!(res.notes(), vec![Note {
+        name: "Foo",
+        note_type: 1,
+        desc: &[0x78, 0x56, 0x34, 0x12],
+        origin: NoteOrigin::Section(".note.rustep"),
+    }]);
+}
@@ -0,0 +1,46 @@
+//! AArch64 relocation types.
+
+reloc_types! {
+    /// AArch64 relocation types, refer to the `R_AARCH64_*` values of `elf.h`
+    RelocAArch64 {
+        R_AARCH64_NONE = 0,
+        R_AARCH64_ABS64 = 257,
+        R_AARCH64_ABS32 = 258,
+        R_AARCH64_ABS16 = 259,
+        R_AARCH64_PREL64 = 260,
+        R_AARCH64_PREL32 = 261,
+        R_AARCH64_PREL16 = 262,
+        R_AARCH64_MOVW_UABS_G0 = 263,
+        R_AARCH64_MOVW_UABS_G0_NC = 264,
+        R_AARCH64_MOVW_UABS_G1 = 265,
+        R_AARCH64_MOVW_UABS_G1_NC = 266,
+        R_AARCH64_MOVW_UABS_G2 = 267,
+        R_AARCH64_MOVW_UABS_G2_NC = 268,
+        R_AARCH64_MOVW_UABS_G3 = 269,
+        R_AARCH64_LD_PREL_LO19 = 273,
+        R_AARCH64_ADR_PREL_LO21 = 274,
+        R_AARCH64_ADR_PREL_PG_HI21 = 275,
+        R_AARCH64_ADR_PREL_PG_HI21_NC = 276,
+        R_AARCH64_ADD_ABS_LO12_NC = 277,
+        R_AARCH64_LDST8_ABS_LO12_NC = 278,
+        R_AARCH64_TSTBR14 = 279,
+        R_AARCH64_CONDBR19 = 280,
+        R_AARCH64_JUMP26 = 282,
+        R_AARCH64_CALL26 = 283,
+        R_AARCH64_LDST16_ABS_LO12_NC = 284,
+        R_AARCH64_LDST32_ABS_LO12_NC = 285,
+        R_AARCH64_LDST64_ABS_LO12_NC = 286,
+        R_AARCH64_LDST128_ABS_LO12_NC = 299,
+        R_AARCH64_ADR_GOT_PAGE = 311,
+        R_AARCH64_LD64_GOT_LO12_NC = 312,
+        R_AARCH64_COPY = 1024,
+        R_AARCH64_GLOB_DAT = 1025,
+        R_AARCH64_JUMP_SLOT = 1026,
+        R_AARCH64_RELATIVE = 1027,
+        R_AARCH64_TLS_DTPMOD = 1028,
+        R_AARCH64_TLS_DTPREL = 1029,
+        R_AARCH64_TLS_TPREL = 1030,
+        R_AARCH64_TLSDESC = 1031,
+        R_AARCH64_IRELATIVE = 1032,
+    }
+}
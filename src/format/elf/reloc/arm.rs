@@ -0,0 +1,41 @@
+//! 32-bit ARM relocation types.
+
+reloc_types! {
+    /// 32-bit ARM relocation types, refer to the `R_ARM_*` values of `elf.h`
+    RelocArm {
+        R_ARM_NONE = 0,
+        R_ARM_PC24 = 1,
+        R_ARM_ABS32 = 2,
+        R_ARM_REL32 = 3,
+        R_ARM_PC13 = 4,
+        R_ARM_ABS16 = 5,
+        R_ARM_ABS12 = 6,
+        R_ARM_THM_ABS5 = 7,
+        R_ARM_ABS8 = 8,
+        R_ARM_SBREL32 = 9,
+        R_ARM_THM_PC22 = 10,
+        R_ARM_THM_PC8 = 11,
+        R_ARM_TLS_DESC = 13,
+        R_ARM_TLS_DTPMOD32 = 17,
+        R_ARM_TLS_DTPOFF32 = 18,
+        R_ARM_TLS_TPOFF32 = 19,
+        R_ARM_COPY = 20,
+        R_ARM_GLOB_DAT = 21,
+        R_ARM_JUMP_SLOT = 22,
+        R_ARM_RELATIVE = 23,
+        R_ARM_GOTOFF = 24,
+        R_ARM_GOTPC = 25,
+        R_ARM_GOT32 = 26,
+        R_ARM_PLT32 = 27,
+        R_ARM_CALL = 28,
+        R_ARM_JUMP24 = 29,
+        R_ARM_THM_JUMP24 = 30,
+        R_ARM_V4BX = 40,
+        R_ARM_PREL31 = 42,
+        R_ARM_MOVW_ABS_NC = 43,
+        R_ARM_MOVT_ABS = 44,
+        R_ARM_THM_MOVW_ABS_NC = 47,
+        R_ARM_THM_MOVT_ABS = 48,
+        R_ARM_IRELATIVE = 160,
+    }
+}
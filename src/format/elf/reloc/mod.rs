@@ -0,0 +1,96 @@
+//! Architecture specific relocation types, giving `ElfRelocation::reloc_type` its meaning.
+use std::borrow::Cow;
+use format::elf::ElfMachine;
+use num_traits::FromPrimitive;
+
+/// Defines a relocation type enum along with a `name` method giving the names without
+/// allocating.
+macro_rules! reloc_types {
+    ($(#[$attr:meta])* $name:ident { $($variant:ident = $value:expr,)* }) => {
+        $(#[$attr])*
+        #[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+        pub enum $name {
+            $($variant = $value,)*
+        }
+
+        impl $name {
+            /// Symbolic name of this type, e.g. `R_X86_64_GLOB_DAT`
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => stringify!($variant),)*
+                }
+            }
+        }
+    };
+}
+
+mod x86_64;
+mod i386;
+mod aarch64;
+mod arm;
+
+pub use self::x86_64::RelocX86_64;
+pub use self::i386::RelocI386;
+pub use self::aarch64::RelocAArch64;
+pub use self::arm::RelocArm;
+
+/// Name of relocation type `reloc_type` on `machine`, e.g. `R_X86_64_GLOB_DAT`. Types we do
+/// not know are rendered with their number, like `R_X86_64_40`, as are all the types of
+/// machines without a relocation enum.
+pub fn reloc_type_name(machine: ElfMachine, reloc_type: u32) -> Cow<'static, str> {
+    let (prefix, name) = match machine {
+        ElfMachine::X86_64 => ("R_X86_64_", RelocX86_64::from_u32(reloc_type).map(|t| t.name())),
+        ElfMachine::I386 => ("R_386_", RelocI386::from_u32(reloc_type).map(|t| t.name())),
+        ElfMachine::AARCH64 => {
+            ("R_AARCH64_", RelocAArch64::from_u32(reloc_type).map(|t| t.name()))
+        },
+        ElfMachine::ARM => ("R_ARM_", RelocArm::from_u32(reloc_type).map(|t| t.name())),
+        _ => return Cow::Owned(format!("R_{:?}_{}", machine, reloc_type)),
+    };
+
+    match name {
+        Some(name) => Cow::Borrowed(name),
+        None => Cow::Owned(format!("{}{}", prefix, reloc_type)),
+    }
+}
+
+#[test]
+fn test_reloc_type_name() {
+    // x86-64
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 1), "R_X86_64_64");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 2), "R_X86_64_PC32");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 4), "R_X86_64_PLT32");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 6), "R_X86_64_GLOB_DAT");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 7), "R_X86_64_JUMP_SLOT");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 8), "R_X86_64_RELATIVE");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 42), "R_X86_64_REX_GOTPCRELX");
+    assert_eq!(reloc_type_name(ElfMachine::X86_64, 40), "R_X86_64_40");
+    // i386
+    assert_eq!(reloc_type_name(ElfMachine::I386, 1), "R_386_32");
+    assert_eq!(reloc_type_name(ElfMachine::I386, 2), "R_386_PC32");
+    assert_eq!(reloc_type_name(ElfMachine::I386, 6), "R_386_GLOB_DAT");
+    assert_eq!(reloc_type_name(ElfMachine::I386, 7), "R_386_JUMP_SLOT");
+    assert_eq!(reloc_type_name(ElfMachine::I386, 8), "R_386_RELATIVE");
+    assert_eq!(reloc_type_name(ElfMachine::I386, 43), "R_386_GOT32X");
+    // AArch64
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 257), "R_AARCH64_ABS64");
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 283), "R_AARCH64_CALL26");
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 1025), "R_AARCH64_GLOB_DAT");
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 1026), "R_AARCH64_JUMP_SLOT");
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 1027), "R_AARCH64_RELATIVE");
+    assert_eq!(reloc_type_name(ElfMachine::AARCH64, 6), "R_AARCH64_6");
+    // ARM
+    assert_eq!(reloc_type_name(ElfMachine::ARM, 2), "R_ARM_ABS32");
+    assert_eq!(reloc_type_name(ElfMachine::ARM, 21), "R_ARM_GLOB_DAT");
+    assert_eq!(reloc_type_name(ElfMachine::ARM, 22), "R_ARM_JUMP_SLOT");
+    assert_eq!(reloc_type_name(ElfMachine::ARM, 23), "R_ARM_RELATIVE");
+    assert_eq!(reloc_type_name(ElfMachine::ARM, 42), "R_ARM_PREL31");
+    // No enum for RISC-V
+    assert_eq!(reloc_type_name(ElfMachine::RISCV, 2), "R_RISCV_2");
+
+    assert_eq!(RelocX86_64::from_u32(9), Some(RelocX86_64::R_X86_64_GOTPCREL));
+    assert!(match reloc_type_name(ElfMachine::ARM, 28) {
+        Cow::Borrowed(name) => name == "R_ARM_CALL",
+        Cow::Owned(_) => false,
+    });
+}
@@ -0,0 +1,348 @@
+//! Relocation entries (`SHT_REL`/`SHT_RELA`) and the PLT resolution built on top of them.
+use format::bindings::*;
+use nom::{IResult::*, Needed::{Size, Unknown}, *};
+use error::RustepError;
+
+/// A single relocation entry. 32-bit and 64-bit entries are widened into this same
+/// representation, `REL` entries have no addend.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ElfRelocation {
+    offset: u64,
+    symbol_index: u32,
+    reloc_type: u32,
+    addend: Option<i64>,
+}
+
+impl ElfRelocation {
+    /// `r_offset`, the location to apply the relocation to
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Symbol index into the symbol table linked (`sh_link`) by the relocation section
+    pub fn symbol_index(&self) -> u32 {
+        self.symbol_index
+    }
+
+    /// Raw architecture specific relocation type
+    pub fn reloc_type(&self) -> u32 {
+        self.reloc_type
+    }
+
+    /// `r_addend`, `None` for `SHT_REL` entries
+    pub fn addend(&self) -> Option<i64> {
+        self.addend
+    }
+}
+
+/// A PLT stub paired with the symbol it jumps to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PltEntry {
+    /// Virtual address of the PLT stub
+    pub address: u64,
+    /// Virtual address of the GOT slot the stub jumps through
+    pub got_address: u64,
+    /// Name of the imported symbol
+    pub symbol: String,
+}
+
+/// A GOT slot, with the symbol the dynamic loader stores there.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GotEntry {
+    /// Virtual address of the slot
+    pub address: u64,
+    /// Value of the slot in the file, before any relocation is applied
+    pub value: u64,
+    /// Name of the symbol relocated into the slot, `None` for reserved slots and relative
+    /// relocations
+    pub symbol: Option<String>,
+}
+
+/// A relocation entry with its type and symbol resolved, as listed by `readelf -r`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolvedReloc {
+    /// Name of the relocation section the entry comes from
+    pub section: String,
+    /// `r_offset`, the location to apply the relocation to
+    pub offset: u64,
+    /// Symbolic name of the relocation type, see `reloc_type_name`
+    pub reloc_type: String,
+    /// Name of the symbol, `None` for relocations without one like `R_X86_64_RELATIVE`
+    pub symbol: Option<String>,
+    /// `r_addend`, `None` for `SHT_REL` entries
+    pub addend: Option<i64>,
+}
+
+/// Parses the content of a `SHT_REL` (`rela == false`) or `SHT_RELA` section, 32-bit version.
+pub(crate) fn parse_relocations32(data: &[u8], rela: bool) -> Result<Vec<ElfRelocation>, RustepError> {
+    if rela {
+        let entries = nom_try!(
+            count!(data, parse_elf_rela32, data.len() / 12),
+            0,
+            "relocation entries"
+        );
+        Ok(entries.iter().map(|r| ElfRelocation {
+            offset: r.r_offset as u64,
+            symbol_index: r.r_info >> 8,
+            reloc_type: r.r_info & 0xff,
+            addend: Some(r.r_addend as i64),
+        }).collect())
+    } else {
+        let entries = nom_try!(
+            count!(data, parse_elf_rel32, data.len() / 8),
+            0,
+            "relocation entries"
+        );
+        Ok(entries.iter().map(|r| ElfRelocation {
+            offset: r.r_offset as u64,
+            symbol_index: r.r_info >> 8,
+            reloc_type: r.r_info & 0xff,
+            addend: None,
+        }).collect())
+    }
+}
+
+/// Parses the content of a `SHT_REL` (`rela == false`) or `SHT_RELA` section, 64-bit version.
+pub(crate) fn parse_relocations64(data: &[u8], rela: bool) -> Result<Vec<ElfRelocation>, RustepError> {
+    if rela {
+        let entries = nom_try!(
+            count!(data, parse_elf_rela64, data.len() / 24),
+            0,
+            "relocation entries"
+        );
+        Ok(entries.iter().map(|r| ElfRelocation {
+            offset: r.r_offset,
+            symbol_index: (r.r_info >> 32) as u32,
+            reloc_type: r.r_info as u32,
+            addend: Some(r.r_addend),
+        }).collect())
+    } else {
+        let entries = nom_try!(
+            count!(data, parse_elf_rel64, data.len() / 16),
+            0,
+            "relocation entries"
+        );
+        Ok(entries.iter().map(|r| ElfRelocation {
+            offset: r.r_offset,
+            symbol_index: (r.r_info >> 32) as u32,
+            reloc_type: r.r_info as u32,
+            addend: None,
+        }).collect())
+    }
+}
+
+// ############### Elf Relocation 32 ################
+named!(parse_elf_rel32<&[u8], Elf32_Rel>,
+    do_parse!(
+        r_offset: le_u32 >>
+        r_info: le_u32 >>
+        (Elf32_Rel {
+            r_offset: r_offset,
+            r_info: r_info
+        })
+    )
+);
+
+named!(parse_elf_rela32<&[u8], Elf32_Rela>,
+    do_parse!(
+        r_offset: le_u32 >>
+        r_info: le_u32 >>
+        r_addend: le_i32 >>
+        (Elf32_Rela {
+            r_offset: r_offset,
+            r_info: r_info,
+            r_addend: r_addend
+        })
+    )
+);
+
+// ############### Elf Relocation 64 ################
+named!(parse_elf_rel64<&[u8], Elf64_Rel>,
+    do_parse!(
+        r_offset: le_u64 >>
+        r_info: le_u64 >>
+        (Elf64_Rel {
+            r_offset: r_offset,
+            r_info: r_info
+        })
+    )
+);
+
+named!(parse_elf_rela64<&[u8], Elf64_Rela>,
+    do_parse!(
+        r_offset: le_u64 >>
+        r_info: le_u64 >>
+        r_addend: le_i64 >>
+        (Elf64_Rela {
+            r_offset: r_offset,
+            r_info: r_info,
+            r_addend: r_addend
+        })
+    )
+);
+
+#[test]
+fn test_relocations() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -r test/test`
+    let relocs = res.relocations(res.section(".rela.dyn").unwrap()).unwrap();
+    assert_eq!(relocs.len(), 9);
+    assert_eq!(relocs[0].offset(), 0x200de0);
+    assert_eq!(relocs[0].reloc_type(), 8);
+    assert_eq!(relocs[0].addend(), Some(0x640));
+    assert_eq!(relocs[5].symbol_index(), 3);
+    assert_eq!(relocs[5].reloc_type(), 6);
+    assert!(res.relocations(res.section(".text").unwrap()).unwrap().is_empty());
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let relocs = res.relocations(res.section(".rel.plt").unwrap()).unwrap();
+    assert_eq!(relocs.len(), 2);
+    assert_eq!(relocs[1].offset(), 0x2010);
+    assert_eq!(relocs[1].symbol_index(), 5);
+    assert_eq!(relocs[1].reloc_type(), 7);
+    assert_eq!(relocs[1].addend(), None);
+}
+
+#[test]
+fn test_plt_entries() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `objdump -d -j .plt test/test`
+    assert_eq!(res.plt_entries(), vec![
+        PltEntry { address: 0x530, got_address: 0x201018, symbol: "printf".to_string() },
+    ]);
+
+    // `.plt` moved to the top of the address space
+    let plt = res.section(".plt").unwrap().index();
+    let sh_addr = res.header().shoff() as usize + plt * res.header().shentsize() as usize + 16;
+    let mut patched = buf.clone();
+    patched[sh_addr..sh_addr + 8].copy_from_slice(&(u64::max_value() - 8).to_le_bytes());
+    let result = parse_elf(&patched).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.plt_entries()[0].address, 7);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(res.plt_entries(), vec![
+        PltEntry { address: 0x3b0, got_address: 0x200c, symbol: "printf".to_string() },
+        PltEntry { address: 0x3c0, got_address: 0x2010, symbol: "__libc_start_main".to_string() },
+    ]);
+}
+
+#[test]
+fn test_got_entries() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -x .got -x .got.plt -r test/test`: 6 slots in `.got` at 0x200fd0,
+    // 4 in `.got.plt` at 0x201000
+    let entries = res.got_entries();
+    assert_eq!(entries.len(), 10);
+    assert_eq!(entries[0], (0x200fd0, 0));
+    assert_eq!(entries[5], (0x200ff8, 0x508));
+    // `.got.plt[0]` is the address of `.dynamic`, the printf slot points back into its stub
+    assert_eq!(entries[6], (0x201000, 0x200df0));
+    assert_eq!(entries[9], (0x201018, 0x536));
+
+    let got = res.got_symbols();
+    assert_eq!(got.len(), 10);
+    assert_eq!(got[1], GotEntry {
+        address: 0x200fd8,
+        value: 0,
+        symbol: Some("__libc_start_main".to_string()),
+    });
+    // `R_X86_64_RELATIVE` has no symbol
+    assert_eq!(got[5].symbol, None);
+    // Reserved
+    assert!(got[6..9].iter().all(|entry| entry.symbol.is_none()));
+    assert_eq!(got[9].symbol, Some("printf".to_string()));
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // 4 byte slots, 5 in `.got` at 0x1fec and 5 in `.got.plt` at 0x2000
+    let got = res.got_symbols();
+    assert_eq!(got.len(), 10);
+    assert_eq!(got[1].address, 0x1ff0);
+    assert_eq!(got[1].symbol, Some("__cxa_finalize".to_string()));
+    assert_eq!(got[9].address, 0x2010);
+    assert_eq!(got[9].symbol, Some("__libc_start_main".to_string()));
+}
+
+#[test]
+fn test_resolved_relocations() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -r test/test`
+    let relocs = res.resolved_relocations();
+    assert_eq!(relocs.len(), 10);
+    assert_eq!(relocs[0], ResolvedReloc {
+        section: ".rela.dyn".to_string(),
+        offset: 0x200de0,
+        reloc_type: "R_X86_64_RELATIVE".to_string(),
+        symbol: None,
+        addend: Some(0x640),
+    });
+    assert_eq!(relocs[5], ResolvedReloc {
+        section: ".rela.dyn".to_string(),
+        offset: 0x200fd8,
+        reloc_type: "R_X86_64_GLOB_DAT".to_string(),
+        symbol: Some("__libc_start_main".to_string()),
+        addend: Some(0),
+    });
+    assert_eq!(relocs[9].section, ".rela.plt");
+    assert_eq!(relocs[9].reloc_type, "R_X86_64_JUMP_SLOT");
+    assert_eq!(relocs[9].symbol, Some("printf".to_string()));
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let relocs = res.resolved_relocations();
+    assert_eq!(relocs.len(), 10);
+    assert_eq!(relocs[5].reloc_type, "R_386_GLOB_DAT");
+    assert_eq!(relocs[5].symbol, Some("__cxa_finalize".to_string()));
+    assert_eq!(relocs[5].addend, None);
+}
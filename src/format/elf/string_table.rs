@@ -0,0 +1,103 @@
+//! String tables (`SHT_STRTAB`), the blobs of NUL terminated strings that section and symbol
+//! names point into.
+use std::str;
+
+/// A view over the contents of a string table section. Each lookup scans from the given offset
+/// to the next NUL, see `IndexedStringTable` for resolving many names of the same table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StringTable<'a>(pub &'a [u8]);
+
+impl<'a> StringTable<'a> {
+    /// Raw bytes of the string starting at `offset`, without the terminating NUL. `None` if
+    /// `offset` is out of the table or the string is not terminated.
+    pub fn get_bytes(&self, offset: u32) -> Option<&'a [u8]> {
+        let rest = self.0.get(offset as usize..)?;
+        let end = rest.iter().position(|&b| b == 0)?;
+
+        Some(&rest[..end])
+    }
+
+    /// The string starting at `offset`. `None` if `offset` is out of the table, the string is
+    /// not terminated or is not valid UTF-8.
+    pub fn get(&self, offset: u32) -> Option<&'a str> {
+        str::from_utf8(self.get_bytes(offset)?).ok()
+    }
+}
+
+/// A string table with the position of every NUL found upfront, used by the parser to resolve
+/// all the section and symbol names. Lookups are a binary search instead of a scan, so names
+/// crafted to all point into one long unterminated run do not rescan it each time.
+pub(crate) struct IndexedStringTable<'a> {
+    data: &'a [u8],
+    nuls: Vec<usize>,
+}
+
+impl<'a> IndexedStringTable<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> IndexedStringTable<'a> {
+        let nuls = data
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == 0)
+            .map(|(i, _)| i)
+            .collect();
+
+        IndexedStringTable {
+            data: data,
+            nuls: nuls,
+        }
+    }
+
+    /// Same as `StringTable::get_bytes`.
+    pub(crate) fn get_bytes(&self, offset: u32) -> Option<&'a [u8]> {
+        let start = offset as usize;
+        let next = match self.nuls.binary_search(&start) {
+            Ok(i) | Err(i) => i,
+        };
+        let end = *self.nuls.get(next)?;
+
+        Some(&self.data[start..end])
+    }
+}
+
+#[test]
+fn test_string_table() {
+    let table = StringTable(b"\0.text\0.data\0unterminated");
+    assert_eq!(table.get(0), Some(""));
+    assert_eq!(table.get(1), Some(".text"));
+    // Offsets may point into the middle of a string, the linker shares suffixes this way
+    assert_eq!(table.get(3), Some("ext"));
+    assert_eq!(table.get(7), Some(".data"));
+    assert_eq!(table.get(13), None);
+    assert_eq!(table.get(26), None);
+    assert_eq!(table.get(0xffffffff), None);
+    assert_eq!(StringTable(b"\xff\0").get(0), None);
+    assert_eq!(StringTable(b"\xff\0").get_bytes(0), Some(&b"\xff"[..]));
+
+    let data = b"\0.text\0.data\0\0unterminated";
+    let indexed = IndexedStringTable::new(data);
+    for offset in (0..data.len() as u32 + 2).chain(Some(0xffffffff)) {
+        assert_eq!(indexed.get_bytes(offset), StringTable(data).get_bytes(offset), "{}", offset);
+    }
+    assert_eq!(IndexedStringTable::new(b"").get_bytes(0), None);
+}
+
+#[test]
+fn test_elf_string_table() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let shstrtab = res.string_table(".shstrtab").unwrap();
+    let text = res.section(".text").unwrap();
+    assert_eq!(shstrtab.get(text.shdr().name_offset()), Some(".text"));
+    let dynstr = res.string_table(".dynstr").unwrap();
+    assert_eq!(dynstr.get(1), Some("libc.so.6"));
+    assert_eq!(dynstr.get(dynstr.0.len() as u32), None);
+    assert!(res.string_table(".text").is_none());
+    assert!(res.string_table(".nonexistent").is_none());
+}
@@ -0,0 +1,517 @@
+//! Symbol table (`.symtab` and `.dynsym`) representation.
+use std::borrow::Cow;
+use format::bindings::*;
+use std::mem;
+use format::elf::{ElfFormat, FromBytes, MaybeKnown, ReservedRanges};
+use nom::*;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// `st_shndx` of undefined symbols
+pub const SHN_UNDEF: u16 = 0;
+/// `st_shndx` of absolute symbols, whose value does not move with relocation
+pub const SHN_ABS: u16 = 0xfff1;
+/// `st_shndx` of common symbols, not yet allocated uninitialized data
+pub const SHN_COMMON: u16 = 0xfff2;
+
+/// Symbol type, the low nibble of `st_info`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SymbolType {
+    STT_NOTYPE = 0,
+    STT_OBJECT = 1,
+    STT_FUNC = 2,
+    STT_SECTION = 3,
+    STT_FILE = 4,
+    STT_COMMON = 5,
+    STT_TLS = 6,
+    /// GNU indirect function, the first value of the OS specific range
+    STT_GNU_IFUNC = 10,
+}
+
+impl ReservedRanges for SymbolType {
+    const RANGES: &'static [(u32, u32, &'static str)] = &[
+        (10, 12, "STT_LOOS"),
+        (13, 15, "STT_LOPROC"),
+    ];
+}
+
+/// Symbol binding, the high nibble of `st_info`
+#[derive(FromPrimitive, ToPrimitive, Eq, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SymbolBinding {
+    STB_LOCAL = 0,
+    STB_GLOBAL = 1,
+    STB_WEAK = 2,
+    /// Unique in the whole process, the first value of the OS specific range
+    STB_GNU_UNIQUE = 10,
+}
+
+impl ReservedRanges for SymbolBinding {
+    const RANGES: &'static [(u32, u32, &'static str)] = &[
+        (10, 12, "STB_LOOS"),
+        (13, 15, "STB_LOPROC"),
+    ];
+}
+
+/// Universal interface of a symbol table entry, ignoring the 32 or 64 part.
+pub trait ElfSymbol {
+    /// Name of the symbol, resolved through the string table linked to the symbol table.
+    fn name(&self) -> &str;
+    /// `st_value`, usually the address of the symbol
+    fn value(&self) -> u64;
+    /// `st_size`
+    fn size(&self) -> u64;
+    /// Raw `st_info`, holding both binding (high nibble) and type (low nibble)
+    fn info(&self) -> u8;
+    /// Raw `st_other`, holding the visibility
+    fn other(&self) -> u8;
+    /// `st_shndx`, index of the section this symbol is defined in
+    fn section_index(&self) -> u16;
+    /// Type of the symbol, from the low nibble of `st_info`. The OS and processor specific
+    /// values other than `STT_GNU_IFUNC` are left `Unknown`.
+    fn sym_type(&self) -> MaybeKnown<SymbolType> {
+        MaybeKnown::from_u32((self.info() & 0xf) as u32)
+    }
+    /// Binding of the symbol, from the high nibble of `st_info`. The OS and processor specific
+    /// values other than `STB_GNU_UNIQUE` are left `Unknown`.
+    fn binding(&self) -> MaybeKnown<SymbolBinding> {
+        MaybeKnown::from_u32((self.info() >> 4) as u32)
+    }
+    /// Whether this is a GNU indirect function (`STT_GNU_IFUNC`): the value is the address of
+    /// a resolver, called at load time to pick the implementation the symbol binds to.
+    fn is_ifunc(&self) -> bool {
+        self.sym_type() == SymbolType::STT_GNU_IFUNC
+    }
+    /// Name of the section this symbol is defined in, looked up in `elf`, which must be the file
+    /// the symbol comes from. Absolute and common symbols give the `*ABS*` and `*COM*` markers
+    /// used by `objdump`, undefined symbols and other reserved indices give `None`.
+    fn section_name<'e>(&self, elf: &'e ElfFormat) -> Option<&'e str> {
+        match self.section_index() {
+            SHN_UNDEF => None,
+            SHN_ABS => Some("*ABS*"),
+            SHN_COMMON => Some("*COM*"),
+            // SHN_LORESERVE and above are not section indices
+            index if index >= 0xff00 => None,
+            index => elf.section_by_index(index as usize).map(|sec| sec.name()),
+        }
+    }
+    /// Name of the symbol with Rust or C++ mangling undone, the raw name when it is not
+    /// mangled. Hashes of Rust legacy symbols are left out.
+    #[cfg(feature = "demangle")]
+    fn demangled_name(&self) -> Cow<str> {
+        demangle(self.name())
+    }
+}
+
+/// Rust legacy symbols are valid Itanium names too, so Rust demangling is tried first.
+#[cfg(feature = "demangle")]
+fn demangle(name: &str) -> Cow<str> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Cow::Owned(format!("{:#}", demangled));
+    }
+    match cpp_demangle::Symbol::new(name) {
+        Ok(symbol) => Cow::Owned(symbol.to_string()),
+        Err(_) => Cow::Borrowed(name),
+    }
+}
+
+/// 32-bit symbol representation
+pub struct ElfSymbol32<'a> {
+    /// Internal raw symbol entry
+    pub(crate) sym: Elf32_Sym,
+    /// Borrowed from the string table, owned after `to_owned` or when not valid UTF-8
+    pub(crate) name: Cow<'a, str>,
+}
+
+/// 64-bit symbol representation
+pub struct ElfSymbol64<'a> {
+    /// Internal raw symbol entry
+    pub(crate) sym: Elf64_Sym,
+    /// Borrowed from the string table, owned after `to_owned` or when not valid UTF-8
+    pub(crate) name: Cow<'a, str>,
+}
+
+impl<'a> ElfSymbol for ElfSymbol32<'a> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn value(&self) -> u64 {
+        self.sym.st_value as u64
+    }
+
+    fn size(&self) -> u64 {
+        self.sym.st_size as u64
+    }
+
+    fn info(&self) -> u8 {
+        self.sym.st_info
+    }
+
+    fn other(&self) -> u8 {
+        self.sym.st_other
+    }
+
+    fn section_index(&self) -> u16 {
+        self.sym.st_shndx
+    }
+}
+
+impl<'a> ElfSymbol for ElfSymbol64<'a> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn value(&self) -> u64 {
+        self.sym.st_value
+    }
+
+    fn size(&self) -> u64 {
+        self.sym.st_size
+    }
+
+    fn info(&self) -> u8 {
+        self.sym.st_info
+    }
+
+    fn other(&self) -> u8 {
+        self.sym.st_other
+    }
+
+    fn section_index(&self) -> u16 {
+        self.sym.st_shndx
+    }
+}
+
+/// The name is left empty, it needs the linked string table.
+impl<'a> FromBytes for ElfSymbol32<'a> {
+    const SIZE: usize = mem::size_of::<Elf32_Sym>();
+    fn from_bytes(bytes: &[u8]) -> Option<ElfSymbol32<'a>> {
+        parse_elf_symbol32(bytes)
+            .to_result()
+            .ok()
+            .map(|sym| ElfSymbol32 { sym: sym, name: Cow::Borrowed("") })
+    }
+}
+
+/// The name is left empty, it needs the linked string table.
+impl<'a> FromBytes for ElfSymbol64<'a> {
+    const SIZE: usize = mem::size_of::<Elf64_Sym>();
+    fn from_bytes(bytes: &[u8]) -> Option<ElfSymbol64<'a>> {
+        parse_elf_symbol64(bytes)
+            .to_result()
+            .ok()
+            .map(|sym| ElfSymbol64 { sym: sym, name: Cow::Borrowed("") })
+    }
+}
+
+// ############### Elf Symbol 32 ################
+named!(pub parse_elf_symbol32<&[u8], Elf32_Sym>,
+    do_parse!(
+        st_name: le_u32 >>
+        st_value: le_u32 >>
+        st_size: le_u32 >>
+        st_info: le_u8 >>
+        st_other: le_u8 >>
+        st_shndx: le_u16 >>
+        (Elf32_Sym {
+            st_name: st_name,
+            st_value: st_value,
+            st_size: st_size,
+            st_info: st_info,
+            st_other: st_other,
+            st_shndx: st_shndx
+        })
+    )
+);
+
+// ############### Elf Symbol 64 ################
+named!(pub parse_elf_symbol64<&[u8], Elf64_Sym>,
+    do_parse!(
+        st_name: le_u32 >>
+        st_info: le_u8 >>
+        st_other: le_u8 >>
+        st_shndx: le_u16 >>
+        st_value: le_u64 >>
+        st_size: le_u64 >>
+        (Elf64_Sym {
+            st_name: st_name,
+            st_info: st_info,
+            st_other: st_other,
+            st_shndx: st_shndx,
+            st_value: st_value,
+            st_size: st_size
+        })
+    )
+);
+
+#[test]
+fn test_symbols() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::{parse_elf, ElfFormat};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -s test/test`
+    let dynsym = res.dynamic_symbols();
+    assert_eq!(dynsym.len(), 7);
+    assert_eq!(dynsym[2].name(), "printf");
+    assert_eq!(dynsym[2].section_index(), 0);
+    let symtab = res.symbols();
+    assert_eq!(symtab.len(), 65);
+    let main = symtab.iter().find(|s| s.name() == "main").unwrap();
+    assert_eq!(main.value(), 0x64a);
+    assert_eq!(main.size(), 47);
+    assert_eq!(main.info(), 0x12);
+    assert_eq!(main.section_index(), 13);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let dynsym = res.dynamic_symbols();
+    assert_eq!(dynsym.len(), 8);
+    assert_eq!(dynsym[5].name(), "__libc_start_main");
+    let main = res.symbols().into_iter().find(|s| s.name() == "main").unwrap();
+    assert_eq!(main.info(), 0x12);
+}
+
+#[test]
+fn test_symbol_section_name() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    let mut file = File::open("test/test_common.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -s test/test_common.o`
+    let symbols = res.symbols();
+    let find = |name: &str| *symbols.iter().find(|s| s.name() == name).unwrap();
+    assert_eq!(find("common.c").section_name(res), Some("*ABS*"));
+    assert_eq!(find("common_var").section_name(res), Some("*COM*"));
+    assert_eq!(find("main").section_name(res), Some(".text"));
+    assert_eq!(symbols[0].section_name(res), None);
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let dynsym = res.dynamic_symbols();
+    let printf = dynsym.iter().find(|s| s.name() == "printf").unwrap();
+    assert_eq!(printf.section_name(res), None);
+}
+
+#[test]
+fn test_symbol_type() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -s test/test`
+    let symbols = res.symbols();
+    let find = |name: &str| *symbols.iter().find(|s| s.name() == name).unwrap();
+    assert_eq!(find("main").sym_type(), MaybeKnown::Known(SymbolType::STT_FUNC));
+    assert!(find("__dso_handle").sym_type() == SymbolType::STT_OBJECT);
+    assert!(find("_edata").sym_type() == SymbolType::STT_NOTYPE);
+    assert!(symbols[1].sym_type() == SymbolType::STT_SECTION);
+    assert!(symbols.iter().any(|s| s.sym_type() == SymbolType::STT_FILE));
+
+    // Only `STT_GNU_IFUNC` is known in the OS specific range
+    let unknown: MaybeKnown<SymbolType> = MaybeKnown::from_u32(11);
+    assert_eq!(unknown, MaybeKnown::Unknown(11));
+    assert_eq!(unknown.to_string(), "STT_LOOS+0x1");
+    assert_eq!(MaybeKnown::<SymbolType>::from_u32(13).to_string(), "STT_LOPROC+0x0");
+}
+
+#[test]
+fn test_symbol_binding() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `readelf -s test/test`
+    let symbols = res.symbols();
+    let find = |name: &str| *symbols.iter().find(|s| s.name() == name).unwrap();
+    assert_eq!(find("main").binding(), MaybeKnown::Known(SymbolBinding::STB_GLOBAL));
+    assert!(find("data_start").binding() == SymbolBinding::STB_WEAK);
+    assert!(find("frame_dummy").binding() == SymbolBinding::STB_LOCAL);
+
+    let unknown: MaybeKnown<SymbolBinding> = MaybeKnown::from_u32(12);
+    assert_eq!(unknown, MaybeKnown::Unknown(12));
+    assert_eq!(unknown.to_string(), "STB_LOOS+0x2");
+}
+
+#[test]
+fn test_ifunc() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    // `answer` is resolved by `resolve_answer` at load time, see `readelf -s test/test_ifunc.o`
+    let mut file = File::open("test/test_ifunc.o").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let symbols = res.symbols();
+    let find = |name: &str| *symbols.iter().find(|s| s.name() == name).unwrap();
+    assert!(find("answer").is_ifunc());
+    assert!(!find("resolve_answer").is_ifunc());
+    assert!(!find("main").is_ifunc());
+    assert!(res.format_symbols().contains("0000000000000006 i answer\n"));
+}
+
+#[test]
+fn test_stripped_dynamic_symbols() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    // Checked with `readelf --dyn-syms test/test_now.so`, there is no `.symtab`
+    let mut file = File::open("test/test_now.so").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert!(res.section(".symtab").is_none());
+    assert!(res.is_stripped());
+    assert!(res.symbols().is_empty());
+    let names: Vec<&str> = res.dynamic_symbols().iter().map(|sym| sym.name()).collect();
+    assert_eq!(names, vec![
+        "",
+        "__cxa_finalize",
+        "_ITM_registerTMCloneTable",
+        "_ITM_deregisterTMCloneTable",
+        "__gmon_start__",
+        "answer",
+    ]);
+    // Read from `.dynstr`
+    let dynstr = res.section(".dynstr").unwrap().data();
+    let dynstr = dynstr.as_ptr() as usize..dynstr.as_ptr() as usize + dynstr.len();
+    for sym in res.dynamic_symbols().into_iter().skip(1) {
+        assert!(dynstr.contains(&(sym.name().as_ptr() as usize)), "{}", sym.name());
+    }
+}
+
+#[test]
+fn test_symbol_names_borrowed() {
+    use std::{fs::File, io::prelude::*};
+    use format::{elf::parse_elf, executable::Executable};
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let elf = match result {
+        Executable::Elf64(elf) => elf,
+        _ => panic!("test/test is a 64-bit ELF"),
+    };
+    let main = elf.symbols.iter().find(|s| s.name() == "main").unwrap();
+    match main.name {
+        Cow::Borrowed(name) => {
+            // Points right into the `.strtab` of the buffer
+            let strtab = elf.section(".strtab").unwrap().shdr().offset() as usize;
+            let offset = name.as_ptr() as usize - buf.as_ptr() as usize;
+            assert_eq!(offset, strtab + main.sym.st_name as usize);
+        },
+        Cow::Owned(_) => panic!("name copied out of the string table"),
+    }
+
+    let owned = elf.to_owned();
+    let main = owned.symbols.iter().find(|s| s.name() == "main").unwrap();
+    assert!(match main.name { Cow::Owned(_) => true, Cow::Borrowed(_) => false });
+}
+
+#[cfg(feature = "demangle")]
+#[test]
+fn test_demangled_name() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+
+    let mut file = File::open("test/test_ctors").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    // Checked with `nm -C test/test_ctors`
+    let symbols = res.symbols();
+    let demangled = |name: &str| {
+        symbols.iter().find(|sym| sym.name() == name).unwrap().demangled_name().into_owned()
+    };
+    assert_eq!(demangled("_ZN7GreeterD2Ev"), "Greeter::~Greeter()");
+    assert_eq!(demangled("_ZL5earlyv"), "early()");
+    assert_eq!(demangled("main"), "main");
+    assert_eq!(demangled("__cxa_atexit@GLIBC_2.2.5"), "__cxa_atexit@GLIBC_2.2.5");
+
+    assert_eq!(demangle("_ZN4core3fmt5write17h0123456789abcdefE"), "core::fmt::write");
+    assert_eq!(
+        demangle("_ZN5alloc3vec12Vec$LT$T$GT$4push17h0123456789abcdefE"),
+        "alloc::vec::Vec<T>::push"
+    );
+    assert_eq!(demangle("_R"), "_R");
+}
+
+#[test]
+fn test_section_entries() {
+    use std::{fs::File, io::prelude::*, convert::TryInto};
+    use format::elf::parse_elf;
+    use error::RustepError;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    let dynsym = res.section(".dynsym").unwrap();
+    let entries = dynsym.entries::<ElfSymbol64>().unwrap();
+    let symbols = res.dynamic_symbols();
+    assert_eq!(entries.len(), 7);
+    assert_eq!(entries.len(), symbols.len());
+    for (entry, sym) in entries.iter().zip(symbols) {
+        assert_eq!(
+            (entry.value(), entry.size(), entry.info()),
+            (sym.value(), sym.size(), sym.info())
+        );
+        assert_eq!(entry.name(), "");
+    }
+    assert_eq!(res.section(".init_array").unwrap().entries::<u64>().unwrap(), vec![0x640]);
+    assert_eq!(dynsym.entries::<ElfSymbol32>().err(), Some(RustepError::SizeMismatch(16, 24)));
+    assert_eq!(
+        res.section(".text").unwrap().entries::<u32>().err(),
+        Some(RustepError::SizeMismatch(4, 0))
+    );
+
+    // Cut `.got`, `sh_size` of section header 21, in the middle of its last slot
+    buf[0x1e40 + 21 * 64 + 32] = 0x2c;
+    let result = parse_elf(&buf).unwrap();
+    let res: &ElfFormat = (&result).try_into().expect("unable to convert");
+    assert_eq!(
+        res.section(".got").unwrap().entries::<u64>().err(),
+        Some(RustepError::PartialEntry { size: 0x2c, entry_size: 8 })
+    );
+}
@@ -2,19 +2,24 @@
 //! [`Executable`](executable/enum.Executable.html) enum to provide functionalities of
 //! parsing various file format.
 
-use failure::Error;
+use format::binary::Binary;
+use format::ar::AR_MAGIC;
 use format::elf::{
     Elf32,
     Elf64,
-    parse_elf,
+    ElfSectionHeader,
+    Endianness,
+    HeaderSummary,
+    parse_elf_headers,
+    parse_elf_with,
 };
 use nom::{
     *,
     Needed::*,
     IResult::*,
 };
-use error::RustepErrorKind;
-use num::FromPrimitive;
+use error::RustepError;
+use num_traits::FromPrimitive;
 
 /// A list of all supported file formats, and the parsed structure within. This is the main
 /// interface of `rustep`.
@@ -23,6 +28,87 @@ pub enum Executable<'a> {
     Elf64(Elf64<'a>),
 }
 
+/// An `Executable` that owns all its data instead of borrowing the parsed buffer. It can be
+/// stored or sent across threads freely. Use [`Executable::to_owned`](enum.Executable.html#method.to_owned)
+/// to get one.
+pub type ExecutableOwned = Executable<'static>;
+
+/// Controls how much work the parser does and how forgiving it is. The defaults parse
+/// everything and accept anything that is not outright broken.
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions {
+    /// Fail on unknown section/segment types and flag bits instead of keeping them as
+    /// `MaybeKnown::Unknown` or raw bits, on section names pointing outside of the
+    /// string table or on `e_shstrndx` pointing to a section which is not a string table
+    /// instead of leaving the names empty, on sections or segments extending past
+    /// the end of the file instead of cutting their contents, on a program header table
+    /// overlapping the ELF header or the data of a segment, and on segments with more file
+    /// than memory contents or an alignment which is not a power of two.
+    pub strict: bool,
+    /// Resolve section and symbol names through the string tables. Names are left empty when
+    /// disabled, which saves the string work when only offsets and sizes matter.
+    pub resolve_names: bool,
+    /// Parse `.symtab` and `.dynsym`.
+    pub parse_symbols: bool,
+    /// Reject files declaring more section headers than this, to bound the work on hostile
+    /// inputs.
+    pub max_sections: usize,
+    /// Reject files declaring more section headers or more program headers than this. Unlike
+    /// the 16 bits header fields, the extended counts kept in the first section header can
+    /// reach billions.
+    pub max_entries: usize,
+    /// Keeps only the sections for which this returns `true`, given the section name (empty
+    /// when `resolve_names` is disabled) and header. The others are neither sliced nor checked
+    /// and are left out of `sections()`, so positions in `sections()` are no longer section
+    /// indices: `sh_link`, `sh_info` and `st_shndx` must be resolved through
+    /// `ElfSection::index` or `ElfFormat::section_by_index`. Symbol names are still read when
+    /// their string table is filtered out, but symbols are only parsed from kept tables.
+    pub section_filter: Option<fn(&str, &ElfSectionHeader) -> bool>,
+}
+
+/// Function pointers have no meaningful equality, `section_filter` is left out of the
+/// comparison.
+impl PartialEq for ParseOptions {
+    fn eq(&self, other: &ParseOptions) -> bool {
+        self.strict == other.strict
+            && self.resolve_names == other.resolve_names
+            && self.parse_symbols == other.parse_symbols
+            && self.max_sections == other.max_sections
+            && self.max_entries == other.max_entries
+    }
+}
+
+impl Eq for ParseOptions {}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            strict: false,
+            resolve_names: true,
+            parse_symbols: true,
+            // `e_shnum` is 16 bits, so no limit by default
+            max_sections: usize::max_value(),
+            max_entries: 100_000,
+            section_filter: None,
+        }
+    }
+}
+
+/// A file format recognized from its magic numbers alone, see `Executable::detect_format`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DetectedFormat {
+    /// `\x7fELF`, with the class and byte order of `e_ident`
+    Elf { is_64bit: bool, endianness: Endianness },
+    /// `MZ` DOS stub whose `e_lfanew` points to a `PE\0\0` signature
+    Pe { pe_offset: u32 },
+    /// Single architecture `Mach-O`, `MH_MAGIC` or `MH_MAGIC_64` in either byte order
+    MachO { is_64bit: bool, endianness: Endianness },
+    /// Universal `Mach-O` (`FAT_MAGIC` or `FAT_MAGIC_64`), always big endian
+    MachOFat { is_64bit: bool, arch_count: u32 },
+    /// `ar` archive, see [`Archive`](../ar/struct.Archive.html)
+    Archive,
+}
+
 #[derive(FromPrimitive, ToPrimitive, Eq, PartialEq)]
 enum ExecutableFormat {
     Elf = 0x464c457f,
@@ -65,23 +151,188 @@ impl<'a> Executable<'a> {
     /// assert_eq!(res.header().elf_type().unwrap(), ElfType::ET_DYN);
     ///
     /// ```
-    pub fn from_u8_array(input: &'a [u8]) -> Result<Executable<'a>, Error> {
-        println!("{:?}", nom_try!(
-            alt!(input, tag!("\x7fELF") | tag!("PE\x00\x00")))
-        );
+    pub fn from_u8_array(input: &'a [u8]) -> Result<Executable<'a>, RustepError> {
+        Executable::from_u8_array_with(input, ParseOptions::default())
+    }
+
+    /// Same as `from_u8_array`, with control over what is parsed and how strictly. See
+    /// [`ParseOptions`](struct.ParseOptions.html).
+    pub fn from_u8_array_with(
+        input: &'a [u8],
+        options: ParseOptions
+    ) -> Result<Executable<'a>, RustepError> {
         // File format detection
-        let res = nom_try!(
-            call!(input, le_u32)
-        ); 
-        // It is safe to use `unwrap()` here, as this should panic when the conversion is wrong.
-        // This denotes the internal bug instead of user fault usage since the signature file
-        // should always be possible to be converted, and the not enough situation is covered
-        // in nom parse part.
-        let format: ExecutableFormat = FromPrimitive::from_u32(res).unwrap();
+        let res = nom_try!(call!(input, le_u32), 0, "file magic");
+        match FromPrimitive::from_u32(res) {
+            Some(ExecutableFormat::Elf) => parse_elf_with(input, &options),
+            // Recognized but not supported yet
+            Some(_) | None => Err(RustepError::NotElf)?,
+        }
+    }
+
+    /// Parses the executable starting at `offset` in `input`, e.g. a member of an `ar` archive
+    /// or a slice of a firmware image, without copying it. Offsets inside an executable are
+    /// relative to its own start, so this is the same as parsing `&input[offset..]`; everything
+    /// found before `offset` is ignored.
+    pub fn from_u8_array_at(input: &'a [u8], offset: usize) -> Result<Executable<'a>, RustepError> {
+        match input.get(offset..) {
+            Some(file) => Executable::from_u8_array(file),
+            None => Err(RustepError::FileTooSmall { needed: offset, got: input.len() })?,
+        }
+    }
+
+    /// Reads only the file header and checks the header tables, without touching the contents
+    /// of the sections and segments or resolving any name. Much cheaper than `from_u8_array`
+    /// when scanning many files for their machine or type.
+    /// # Examples
+    /// ```
+    /// use std::fs::File;
+    /// use std::io::prelude::*;
+    /// use rustep::format::executable::Executable;
+    /// use rustep::format::elf::{ElfMachine, ElfType};
+    ///
+    /// let mut file = File::open("test/test").unwrap();
+    /// let mut buf = Vec::new();
+    /// file.read_to_end(&mut buf).unwrap();
+    ///
+    /// let summary = Executable::parse_headers_only(&buf).unwrap();
+    /// assert_eq!(summary.machine, ElfMachine::X86_64);
+    /// assert_eq!(summary.elf_type, ElfType::ET_DYN);
+    /// assert!(summary.is_64bit);
+    /// ```
+    pub fn parse_headers_only(input: &[u8]) -> Result<HeaderSummary, RustepError> {
+        let res = nom_try!(call!(input, le_u32), 0, "file magic");
+        match FromPrimitive::from_u32(res) {
+            Some(ExecutableFormat::Elf) => parse_elf_headers(input),
+            // Recognized but not supported yet
+            Some(_) | None => Err(RustepError::NotElf)?,
+        }
+    }
+
+    /// Guesses the format of `input` from its magic numbers, without parsing anything else, e.g.
+    /// to pick a parser or skip unsupported files before `from_u8_array`. `None` when nothing
+    /// matches. Only `ELF` can be parsed for now.
+    ///
+    /// Java class files share `FAT_MAGIC`, they are told apart by the architecture count,
+    /// which is where their version lies: any count above 30 is taken as a class file.
+    /// # Examples
+    /// ```
+    /// use rustep::format::executable::{DetectedFormat, Executable};
+    /// use rustep::format::elf::Endianness;
+    ///
+    /// let format = Executable::detect_format(b"\x7fELF\x02\x01\x01");
+    /// assert_eq!(format, Some(DetectedFormat::Elf { is_64bit: true, endianness: Endianness::Little }));
+    /// assert_eq!(Executable::detect_format(b"#!/bin/sh"), None);
+    /// ```
+    pub fn detect_format(input: &[u8]) -> Option<DetectedFormat> {
+        // Big endian, the byte order `Mach-O` magics are defined in
+        let be_u32 = |offset: usize| {
+            input.get(offset..offset + 4)
+                .map(|b| (b[0] as u32) << 24 | (b[1] as u32) << 16 | (b[2] as u32) << 8 | b[3] as u32)
+        };
+        let magic = be_u32(0)?;
 
-        match format {
-            ExecutableFormat::Elf => parse_elf(input),
-            _ => panic!("File format other than ELF is not yet supported"),
+        if input.starts_with(AR_MAGIC) {
+            return Some(DetectedFormat::Archive);
+        }
+        if input.starts_with(b"\x7fELF") {
+            let is_64bit = match input.get(4) {
+                Some(&1) => false,
+                Some(&2) => true,
+                _ => return None,
+            };
+            let endianness = match input.get(5) {
+                Some(&1) => Endianness::Little,
+                Some(&2) => Endianness::Big,
+                _ => return None,
+            };
+            return Some(DetectedFormat::Elf { is_64bit: is_64bit, endianness: endianness });
+        }
+        if input.starts_with(b"MZ") {
+            // `e_lfanew`, little endian at the end of the DOS header
+            let pe_offset = be_u32(0x3c)?.swap_bytes();
+            let signature = input.get(pe_offset as usize..)?;
+            return if signature.starts_with(b"PE\0\0") {
+                Some(DetectedFormat::Pe { pe_offset: pe_offset })
+            } else {
+                None
+            };
+        }
+        match magic {
+            0xfeedface => Some(DetectedFormat::MachO { is_64bit: false, endianness: Endianness::Big }),
+            0xfeedfacf => Some(DetectedFormat::MachO { is_64bit: true, endianness: Endianness::Big }),
+            0xcefaedfe => Some(DetectedFormat::MachO { is_64bit: false, endianness: Endianness::Little }),
+            0xcffaedfe => Some(DetectedFormat::MachO { is_64bit: true, endianness: Endianness::Little }),
+            0xcafebabe | 0xcafebabf => {
+                let arch_count = be_u32(4)?;
+                if arch_count > 30 {
+                    return None;
+                }
+                Some(DetectedFormat::MachOFat { is_64bit: magic == 0xcafebabf, arch_count: arch_count })
+            },
+            _ => None,
+        }
+    }
+
+    /// Copies every borrowed part out of the input buffer, the result is no longer tied to the
+    /// lifetime of the buffer.
+    pub fn to_owned(&self) -> ExecutableOwned {
+        match *self {
+            Executable::Elf32(ref elf) => Executable::Elf32(elf.to_owned()),
+            Executable::Elf64(ref elf) => Executable::Elf64(elf.to_owned()),
+        }
+    }
+
+    /// The format agnostic interface, see [`Binary`](../binary/trait.Binary.html).
+    pub fn as_binary(&self) -> &Binary {
+        match *self {
+            Executable::Elf32(ref elf) => elf,
+            Executable::Elf64(ref elf) => elf,
+        }
+    }
+
+    /// The 32-bit `ELF` file, `None` for any other format or class.
+    pub fn as_elf32(&self) -> Option<&Elf32<'a>> {
+        match *self {
+            Executable::Elf32(ref elf) => Some(elf),
+            _ => None,
+        }
+    }
+
+    /// The 64-bit `ELF` file, `None` for any other format or class.
+    pub fn as_elf64(&self) -> Option<&Elf64<'a>> {
+        match *self {
+            Executable::Elf64(ref elf) => Some(elf),
+            _ => None,
+        }
+    }
+
+    /// Short name of the detected format and class, e.g. `"ELF64"`.
+    pub fn format_name(&self) -> &'static str {
+        match *self {
+            Executable::Elf32(_) => "ELF32",
+            Executable::Elf64(_) => "ELF64",
+        }
+    }
+
+    /// Whether this is an `ELF` file, of any class.
+    pub fn is_elf(&self) -> bool {
+        match *self {
+            Executable::Elf32(_) | Executable::Elf64(_) => true,
+        }
+    }
+
+    /// Whether this is a `PE` file. Always `false` for now as only `ELF` is parsed.
+    pub fn is_pe(&self) -> bool {
+        match *self {
+            Executable::Elf32(_) | Executable::Elf64(_) => false,
+        }
+    }
+
+    /// Whether this is a `Mach-O` file. Always `false` for now as only `ELF` is parsed.
+    pub fn is_macho(&self) -> bool {
+        match *self {
+            Executable::Elf32(_) | Executable::Elf64(_) => false,
         }
     }
 
@@ -102,4 +353,271 @@ fn test_executable() {
         Executable::Elf64(_elf) => {},
         _ => { panic!("Wrong file format detection") }
     }
+
+    // Other formats are not supported yet
+    for magic in [&b"\xcf\xfa\xed\xfe"[..], b"PE\0\0", b"#!/bin/sh"].iter() {
+        let mut input = magic.to_vec();
+        input.extend_from_slice(&[0; 0x40]);
+        match Executable::from_u8_array(&input) {
+            Err(e) => assert_eq!(e, RustepError::NotElf),
+            Ok(_) => panic!("{:?} accepted", magic),
+        }
+    }
+    match Executable::from_u8_array(b"\x7fE") {
+        Err(RustepError::Parse { offset, context, .. }) => {
+            assert_eq!((offset, context), (0, "file magic"))
+        },
+        _ => panic!("truncated magic accepted"),
+    }
+}
+
+#[test]
+fn test_as_elf() {
+    use std::{
+        fs::File,
+        io::prelude::*,
+    };
+    use format::elf::ElfFormat;
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let res = Executable::from_u8_array(&buf).unwrap();
+    assert!(res.as_elf32().is_none());
+    let elf = res.as_elf64().unwrap();
+    assert!(ElfFormat::is_64bit(elf));
+    assert_eq!(elf.header().entry(), 0x540);
+
+    let mut file = File::open("test/test32").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let res = Executable::from_u8_array(&buf).unwrap();
+    assert!(res.as_elf64().is_none());
+    let elf = res.as_elf32().unwrap();
+    assert!(!ElfFormat::is_64bit(elf));
+    assert!(elf.section(".text").is_some());
+}
+
+#[test]
+fn test_from_u8_array_at() {
+    use std::{
+        convert::TryInto,
+        fs::File,
+        io::prelude::*,
+    };
+    use format::elf::ElfFormat;
+
+    // `ar` archive of `test_common.o` and `test_debug.o`, checked with `ar tv` and the member
+    // headers: their contents start at 0xa6 and 0x4c2
+    let mut file = File::open("test/test_archive.a").unwrap();
+    let mut archive = Vec::new();
+    file.read_to_end(&mut archive).unwrap();
+
+    for &(offset, path) in [(0xa6, "test/test_common.o"), (0x4c2, "test/test_debug.o")].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+        let alone = Executable::from_u8_array(&buf).unwrap();
+        let alone: &ElfFormat = (&alone).try_into().expect("unable to convert");
+
+        let member = Executable::from_u8_array_at(&archive, offset).unwrap();
+        let member: &ElfFormat = (&member).try_into().expect("unable to convert");
+        assert_eq!(member.sections().len(), alone.sections().len(), "{}", path);
+        for (sec, expected) in member.sections().iter().zip(alone.sections()) {
+            assert_eq!(sec.name(), expected.name(), "{}", path);
+            assert_eq!(sec.data(), expected.data(), "{}", path);
+        }
+        // Borrowed right from the archive
+        let text = member.section(".text").unwrap();
+        let text_offset = text.data().as_ptr() as usize - archive.as_ptr() as usize;
+        assert_eq!(text_offset, offset + text.shdr().offset() as usize, "{}", path);
+    }
+
+    match Executable::from_u8_array_at(&archive, archive.len() + 1) {
+        Err(e) => assert_eq!(e, RustepError::FileTooSmall {
+            needed: archive.len() + 1,
+            got: archive.len(),
+        }),
+        Ok(_) => panic!("offset past the end accepted"),
+    }
+}
+
+#[test]
+fn test_format_name() {
+    use std::{
+        fs::File,
+        io::prelude::*,
+    };
+
+    for &(path, name) in [
+        ("test/test", "ELF64"),
+        ("test/test32", "ELF32"),
+        ("test/test_core", "ELF64"),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let res = Executable::from_u8_array(&buf).unwrap();
+        assert_eq!(res.format_name(), name);
+        assert!(res.is_elf());
+        assert!(!res.is_pe());
+        assert!(!res.is_macho());
+    }
+}
+
+#[test]
+fn test_parse_headers_only() {
+    use std::{
+        convert::TryInto,
+        fs::File,
+        io::prelude::*,
+    };
+    use format::elf::{ElfFormat, MaybeKnown};
+
+    for path in ["test/test", "test/test32", "test/test_core", "test/test_riscv64.o"].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        let summary = Executable::parse_headers_only(&buf).unwrap();
+        let tables = {
+            let full = Executable::from_u8_array(&buf).unwrap();
+            let res: &ElfFormat = (&full).try_into().expect("unable to convert");
+            let hdr = res.header();
+            assert_eq!(summary.is_64bit, res.is_64bit(), "{}", path);
+            assert_eq!(summary.endianness, res.endianness(), "{}", path);
+            assert_eq!(summary.elf_type, hdr.elf_type().unwrap(), "{}", path);
+            assert_eq!(summary.machine, MaybeKnown::Known(hdr.machine().unwrap()), "{}", path);
+            assert_eq!(summary.entry, hdr.entry(), "{}", path);
+            assert_eq!(summary.flags, hdr.flags(), "{}", path);
+            assert_eq!(summary.segment_count, res.segments().len(), "{}", path);
+            assert_eq!(summary.section_count, res.sections().len(), "{}", path);
+
+            [
+                (0, hdr.ehsize()),
+                (hdr.phoff(), hdr.phoff() + hdr.phnum() * hdr.phentsize()),
+                (hdr.shoff(), hdr.shoff() + hdr.shnum() * hdr.shentsize()),
+            ]
+        };
+
+        // Wipe everything but the headers, the summary does not change
+        for (offset, byte) in buf.iter_mut().enumerate() {
+            let offset = offset as u64;
+            if !tables.iter().any(|&(start, end)| offset >= start && offset < end) {
+                *byte = 0xff;
+            }
+        }
+        assert_eq!(Executable::parse_headers_only(&buf).unwrap(), summary, "{}", path);
+    }
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+    // The section header table is at the end of the file
+    let truncated = &buf[..buf.len() - 1];
+    match Executable::parse_headers_only(truncated) {
+        Err(e) => match e {
+            RustepError::Parse { context, .. } => assert_eq!(context, "section header table"),
+            kind => panic!("unexpected error {:?}", kind),
+        },
+        Ok(_) => panic!("truncated section header table accepted"),
+    }
+    match Executable::parse_headers_only(b"\0\0\0\0 not an executable") {
+        Err(e) => assert_eq!(e, RustepError::NotElf),
+        Ok(_) => panic!("garbage accepted"),
+    }
+    // Little endian 64 and 32 bit Mach-O, then PE
+    for magic in [&b"\xcf\xfa\xed\xfe"[..], b"\xce\xfa\xed\xfe", b"PE\0\0"].iter() {
+        let mut input = magic.to_vec();
+        input.extend_from_slice(&[0; 0x40]);
+        match Executable::parse_headers_only(&input) {
+            Err(e) => assert_eq!(e, RustepError::NotElf),
+            Ok(_) => panic!("{:?} accepted", magic),
+        }
+    }
+}
+
+#[test]
+fn test_detect_format() {
+    use std::{
+        fs::File,
+        io::prelude::*,
+    };
+
+    for &(path, expected) in [
+        ("test/test", DetectedFormat::Elf { is_64bit: true, endianness: Endianness::Little }),
+        ("test/test32", DetectedFormat::Elf { is_64bit: false, endianness: Endianness::Little }),
+        ("test/test_archive.a", DetectedFormat::Archive),
+    ].iter() {
+        let mut file = File::open(path).unwrap();
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        assert_eq!(Executable::detect_format(&buf), Some(expected), "{}", path);
+    }
+    let big = b"\x7fELF\x01\x02\x01\0";
+    assert_eq!(
+        Executable::detect_format(big),
+        Some(DetectedFormat::Elf { is_64bit: false, endianness: Endianness::Big })
+    );
+    assert_eq!(Executable::detect_format(b"\x7fELF\x03\x01"), None);
+    assert_eq!(Executable::detect_format(b"\x7fELF"), None);
+
+    // DOS stub, `e_lfanew` at 0x3c, then the signature
+    let mut pe = vec![0u8; 0x80];
+    pe[..2].copy_from_slice(b"MZ");
+    pe[0x3c] = 0x40;
+    pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+    assert_eq!(Executable::detect_format(&pe), Some(DetectedFormat::Pe { pe_offset: 0x40 }));
+    // A plain DOS program, and a PE offset past the end
+    pe[0x40] = 0;
+    assert_eq!(Executable::detect_format(&pe), None);
+    pe[0x3d] = 0x10;
+    assert_eq!(Executable::detect_format(&pe), None);
+
+    for &(magic, expected) in [
+        (b"\xfe\xed\xfa\xce", DetectedFormat::MachO { is_64bit: false, endianness: Endianness::Big }),
+        (b"\xfe\xed\xfa\xcf", DetectedFormat::MachO { is_64bit: true, endianness: Endianness::Big }),
+        (b"\xce\xfa\xed\xfe", DetectedFormat::MachO { is_64bit: false, endianness: Endianness::Little }),
+        (b"\xcf\xfa\xed\xfe", DetectedFormat::MachO { is_64bit: true, endianness: Endianness::Little }),
+    ].iter() {
+        assert_eq!(Executable::detect_format(magic), Some(expected));
+    }
+    assert_eq!(
+        Executable::detect_format(b"\xca\xfe\xba\xbe\0\0\0\x02"),
+        Some(DetectedFormat::MachOFat { is_64bit: false, arch_count: 2 })
+    );
+    assert_eq!(
+        Executable::detect_format(b"\xca\xfe\xba\xbf\0\0\0\x01"),
+        Some(DetectedFormat::MachOFat { is_64bit: true, arch_count: 1 })
+    );
+    // Java class file, version 52
+    assert_eq!(Executable::detect_format(b"\xca\xfe\xba\xbe\0\0\0\x34"), None);
+
+    assert_eq!(Executable::detect_format(b"\0\0\0\0 not an executable"), None);
+    assert_eq!(Executable::detect_format(b""), None);
+}
+
+#[test]
+fn test_executable_with_options() {
+    use std::{
+        fs::File,
+        io::prelude::*,
+    };
+
+    let mut file = File::open("test/test").unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    let options = ParseOptions { max_sections: 4, ..ParseOptions::default() };
+    match Executable::from_u8_array_with(&buf, options) {
+        Err(e) => assert_eq!(
+            e,
+            RustepError::TooManySections(30)
+        ),
+        Ok(_) => panic!("section limit ignored"),
+    }
 }
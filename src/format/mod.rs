@@ -8,3 +8,7 @@
 mod bindings;
 pub mod executable;
 pub mod elf;
+pub mod binary;
+pub mod ar;
+#[cfg(feature = "serde")]
+mod serialize;
@@ -0,0 +1,96 @@
+//! `serde` support for the parsed structures, only compiled with the `serde` feature.
+//!
+//! Most of the public types are trait objects over the bindgen generated structs, so instead of
+//! deriving on the raw `ElfXX_*` structs we serialize through the trait methods. This keeps the
+//! 32-bit and 64-bit output identical in shape.
+use bitflags::Flags;
+use serde::{
+    Serialize,
+    Serializer,
+    ser::SerializeStruct,
+};
+use format::elf::{
+    ElfFormat,
+    ElfHeader,
+    ElfSection,
+    ElfSegment,
+    MaybeKnown,
+};
+
+/// Wrapper used by `ElfFormat::to_json` so that the default method can serialize `Self` even
+/// when it is unsized.
+pub struct ElfFormatSerializer<'a, T: ?Sized + 'a>(pub &'a T);
+
+impl<'a, T: ElfFormat + ?Sized> Serialize for ElfFormatSerializer<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Elf", 3)?;
+        state.serialize_field("header", self.0.header())?;
+        state.serialize_field("segments", &self.0.segments())?;
+        state.serialize_field("sections", &self.0.sections())?;
+        state.end()
+    }
+}
+
+impl<T: Serialize> Serialize for MaybeKnown<T> {
+    /// Known values are written as their symbolic name, unknown ones as the raw number.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            MaybeKnown::Known(ref known) => known.serialize(serializer),
+            MaybeKnown::Unknown(value) => serializer.serialize_u32(value),
+        }
+    }
+}
+
+impl<'a> Serialize for ElfHeader + 'a {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ElfHeader", 11)?;
+        // Unknown values are kept as `null` rather than failing the whole dump.
+        state.serialize_field("type", &self.elf_type().ok())?;
+        state.serialize_field("machine", &self.machine().ok())?;
+        state.serialize_field("entry", &self.entry())?;
+        state.serialize_field("phoff", &self.phoff())?;
+        state.serialize_field("shoff", &self.shoff())?;
+        state.serialize_field("ehsize", &self.ehsize())?;
+        state.serialize_field("phentsize", &self.phentsize())?;
+        state.serialize_field("phnum", &self.phnum())?;
+        state.serialize_field("shentsize", &self.shentsize())?;
+        state.serialize_field("shnum", &self.shnum())?;
+        state.serialize_field("shstrndx", &self.shstrndx())?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for ElfSection + 'a {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let shdr = self.shdr();
+        let mut state = serializer.serialize_struct("ElfSection", 7)?;
+        state.serialize_field("name", self.name())?;
+        state.serialize_field("type", self.section_type())?;
+        state.serialize_field("flags", &flag_names(self.flags()))?;
+        state.serialize_field("address", &shdr.address())?;
+        state.serialize_field("offset", &shdr.offset())?;
+        state.serialize_field("size", &shdr.size())?;
+        state.serialize_field("entry_size", &shdr.entry_size())?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for ElfSegment + 'a {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let phdr = self.phdr();
+        let mut state = serializer.serialize_struct("ElfSegment", 7)?;
+        state.serialize_field("type", self.segment_type())?;
+        state.serialize_field("flags", &flag_names(self.flags()))?;
+        state.serialize_field("offset", &phdr.offset())?;
+        state.serialize_field("vaddr", &phdr.vaddr())?;
+        state.serialize_field("paddr", &phdr.paddr())?;
+        state.serialize_field("file_size", &phdr.file_size())?;
+        state.serialize_field("mem_size", &phdr.mem_size())?;
+        state.end()
+    }
+}
+
+/// Flags are written as the list of their known names, like enums are.
+fn flag_names<F: Flags>(flags: F) -> Vec<&'static str> {
+    flags.iter_names().map(|(name, _)| name).collect()
+}
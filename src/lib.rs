@@ -47,24 +47,35 @@
 //! [`ElfFormat`](format/elf/trait.ElfFormat.html) trait object. Please refer to that doc
 //! for more information.
 #![feature(try_from)]
-#[macro_use]
-extern crate nom;
 
 #[macro_use]
-extern crate failure;
+extern crate nom;
 
-extern crate enumflags;
 #[macro_use]
-extern crate enumflags_derive;
+extern crate bitflags;
 
-extern crate num;
+extern crate num_traits;
 #[macro_use]
 extern crate num_derive;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "compression")]
+extern crate flate2;
+#[cfg(feature = "hash")]
+extern crate sha2;
+#[cfg(feature = "demangle")]
+extern crate cpp_demangle;
+#[cfg(feature = "demangle")]
+extern crate rustc_demangle;
+
 #[macro_use]
 pub mod error;
 //pub mod parser;
 pub mod format;
+pub mod disasm;
 
 
 #[cfg(test)]